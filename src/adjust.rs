@@ -0,0 +1,148 @@
+use crate::EncodedColor;
+
+impl EncodedColor {
+    /// Inverts the r, g, and b channels (`255 - c`), keeping alpha. This is the same invert
+    /// image editors do, which works on the encoded values.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn invert(self) -> Self {
+        Self {
+            r: 255 - self.r,
+            g: 255 - self.g,
+            b: 255 - self.b,
+            a: self.a,
+        }
+    }
+
+    /// Adds the bytes of `other` to this color's, per channel (including alpha), stopping at
+    /// 255 rather than wrapping.
+    ///
+    /// **This is in encoded (gamma) space**, so it doesn't add light the way
+    /// [LinearColor::saturating_add](crate::LinearColor::saturating_add) does: the result is
+    /// brighter than real light would be for dark colors. That's fine for quick effects, like
+    /// flashing a sprite, where it just needs to look brighter.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn saturating_add(self, other: EncodedColor) -> Self {
+        Self {
+            r: self.r.saturating_add(other.r),
+            g: self.g.saturating_add(other.g),
+            b: self.b.saturating_add(other.b),
+            a: self.a.saturating_add(other.a),
+        }
+    }
+
+    /// Subtracts the bytes of `other` from this color's, per channel (including alpha),
+    /// stopping at 0 rather than wrapping. Like [saturating_add](Self::saturating_add), **this is
+    /// in encoded (gamma) space**.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn saturating_sub(self, other: EncodedColor) -> Self {
+        Self {
+            r: self.r.saturating_sub(other.r),
+            g: self.g.saturating_sub(other.g),
+            b: self.b.saturating_sub(other.b),
+            a: self.a.saturating_sub(other.a),
+        }
+    }
+
+    /// Applies a tone curve to the r, g, and b channels, by looking each one up in `lut`.
+    /// Alpha is untouched.
+    ///
+    /// This is the building block for curves, levels, contrast, and brightness adjustments.
+    /// It works on encoded values on purpose, since that's the space those curves are authored
+    /// in (like in an image editor's curves tool).
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn apply_curve(self, lut: &[u8; 256]) -> Self {
+        Self {
+            r: lut[self.r as usize],
+            g: lut[self.g as usize],
+            b: lut[self.b as usize],
+            a: self.a,
+        }
+    }
+
+    /// Applies a tone curve to every pixel in `pixels`, in place. See
+    /// [apply_curve](Self::apply_curve).
+    pub fn apply_curve_slice(pixels: &mut [EncodedColor], lut: &[u8; 256]) {
+        for pixel in pixels {
+            *pixel = pixel.apply_curve(lut);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invert() {
+        assert_eq!(EncodedColor::WHITE.invert(), EncodedColor::BLACK);
+        assert_eq!(
+            EncodedColor::new(107, 158, 190, 20).invert(),
+            EncodedColor::new(148, 97, 65, 20)
+        );
+    }
+
+    #[test]
+    fn saturating_math() {
+        let a = EncodedColor::new(200, 10, 128, 255);
+        let b = EncodedColor::new(100, 20, 127, 1);
+
+        assert_eq!(a.saturating_add(b), EncodedColor::new(255, 30, 255, 255));
+        assert_eq!(a.saturating_sub(b), EncodedColor::new(100, 0, 1, 254));
+        assert_eq!(b.saturating_sub(a), EncodedColor::new(0, 10, 0, 0));
+
+        // each channel saturates on its own
+        assert_eq!(
+            EncodedColor::new(255, 0, 0, 0).saturating_add(EncodedColor::new(1, 0, 0, 0)),
+            EncodedColor::new(255, 0, 0, 0)
+        );
+        assert_eq!(
+            EncodedColor::new(0, 0, 5, 0).saturating_sub(EncodedColor::new(0, 0, 6, 0)),
+            EncodedColor::CLEAR
+        );
+        assert_eq!(
+            EncodedColor::WHITE.saturating_add(EncodedColor::WHITE),
+            EncodedColor::WHITE
+        );
+        assert_eq!(
+            EncodedColor::WHITE.saturating_sub(EncodedColor::WHITE),
+            EncodedColor::CLEAR
+        );
+        assert_eq!(a.saturating_add(EncodedColor::CLEAR), a);
+    }
+
+    #[test]
+    fn apply_curve() {
+        let mut inverting = [0; 256];
+        for (i, v) in inverting.iter_mut().enumerate() {
+            *v = 255 - i as u8;
+        }
+
+        let mut identity = [0; 256];
+        for (i, v) in identity.iter_mut().enumerate() {
+            *v = i as u8;
+        }
+
+        let mut pixels = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::TEAL,
+            EncodedColor::CLEAR,
+            EncodedColor::new(1, 2, 3, 4),
+        ];
+        for pixel in pixels {
+            assert_eq!(pixel.apply_curve(&inverting), pixel.invert());
+            assert_eq!(pixel.apply_curve(&identity), pixel);
+        }
+
+        let original = pixels;
+        EncodedColor::apply_curve_slice(&mut pixels, &inverting);
+        for (pixel, original) in pixels.iter().zip(original) {
+            assert_eq!(*pixel, original.invert());
+        }
+
+        // a curve that crushes everything to black keeps alpha
+        assert_eq!(
+            EncodedColor::WHITE.with_a(9).apply_curve(&[0; 256]),
+            EncodedColor::new(0, 0, 0, 9)
+        );
+    }
+}
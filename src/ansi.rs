@@ -0,0 +1,143 @@
+use crate::EncodedColor;
+
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+
+/// The ANSI escape sequence which resets the terminal's colors back to their defaults.
+/// Print this after any of the `ansi_` sequences to stop coloring text.
+pub const ANSI_RESET: &str = "\x1b[0m";
+
+impl EncodedColor {
+    /// Creates the 24-bit ANSI escape sequence which sets the terminal's background color to
+    /// this color. Terminals have no notion of alpha, so it's ignored.
+    ///
+    /// This is handy for printing color swatches in CLI tools, like so:
+    ///
+    /// ```
+    /// # use smol_rgb::{EncodedColor, ANSI_RESET};
+    /// let swatch = EncodedColor::FUCHSIA.ansi_truecolor_bg();
+    /// println!("{}    {}", swatch, ANSI_RESET);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn ansi_truecolor_bg(self) -> String {
+        format!("\x1b[48;2;{};{};{}m", self.r, self.g, self.b)
+    }
+
+    /// Creates the 24-bit ANSI escape sequence which sets the terminal's foreground (text) color
+    /// to this color. Terminals have no notion of alpha, so it's ignored.
+    #[cfg(feature = "alloc")]
+    pub fn ansi_truecolor_fg(self) -> String {
+        format!("\x1b[38;2;{};{};{}m", self.r, self.g, self.b)
+    }
+}
+
+impl EncodedColor {
+    /// Finds the nearest color in the xterm 256 color palette, for terminals which
+    /// don't support truecolor. Alpha is ignored.
+    ///
+    /// This only picks from the 6x6x6 color cube and the grayscale ramp (indices 16 through 255).
+    /// The first 16 colors are the terminal's "system" colors, which most terminals let users
+    /// theme, so we can't know what they'll actually look like. Nearness is measured
+    /// perceptually, in [Oklab](crate::Oklab).
+    ///
+    /// To use the index, print `\x1b[38;5;{index}m` for the foreground or `\x1b[48;5;{index}m`
+    /// for the background.
+    pub fn to_ansi256(self) -> u8 {
+        let target = self.to_oklab();
+
+        let mut best = 16;
+        let mut best_distance = f32::INFINITY;
+        for index in 16..=255 {
+            let distance = EncodedColor::from_ansi256(index).to_oklab().distance_squared(target);
+            if distance < best_distance {
+                best = index;
+                best_distance = distance;
+            }
+        }
+
+        best
+    }
+
+    /// Gets the color of an index in the xterm 256 color palette, with full alpha.
+    ///
+    /// The first 16 colors are the terminal's "system" colors, which users can often theme. For
+    /// those, we return xterm's defaults.
+    pub const fn from_ansi256(index: u8) -> EncodedColor {
+        match index {
+            0..=15 => ANSI_SYSTEM_COLORS[index as usize],
+            16..=231 => {
+                let cube = index - 16;
+
+                EncodedColor::new(
+                    ANSI_CUBE_LEVELS[(cube / 36) as usize],
+                    ANSI_CUBE_LEVELS[(cube / 6 % 6) as usize],
+                    ANSI_CUBE_LEVELS[(cube % 6) as usize],
+                    255,
+                )
+            }
+            232..=255 => {
+                let gray = 8 + (index - 232) * 10;
+
+                EncodedColor::new(gray, gray, gray, 255)
+            }
+        }
+    }
+}
+
+const ANSI_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+const ANSI_SYSTEM_COLORS: [EncodedColor; 16] = [
+    EncodedColor::new(0, 0, 0, 255),
+    EncodedColor::new(128, 0, 0, 255),
+    EncodedColor::new(0, 128, 0, 255),
+    EncodedColor::new(128, 128, 0, 255),
+    EncodedColor::new(0, 0, 128, 255),
+    EncodedColor::new(128, 0, 128, 255),
+    EncodedColor::new(0, 128, 128, 255),
+    EncodedColor::new(192, 192, 192, 255),
+    EncodedColor::new(128, 128, 128, 255),
+    EncodedColor::new(255, 0, 0, 255),
+    EncodedColor::new(0, 255, 0, 255),
+    EncodedColor::new(255, 255, 0, 255),
+    EncodedColor::new(0, 0, 255, 255),
+    EncodedColor::new(255, 0, 255, 255),
+    EncodedColor::new(0, 255, 255, 255),
+    EncodedColor::new(255, 255, 255, 255),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn truecolor() {
+        let cornwall_blue = EncodedColor::new(107, 158, 190, 255);
+
+        assert_eq!(cornwall_blue.ansi_truecolor_bg(), "\x1b[48;2;107;158;190m");
+        assert_eq!(cornwall_blue.ansi_truecolor_fg(), "\x1b[38;2;107;158;190m");
+        assert_eq!(cornwall_blue.with_a(0).ansi_truecolor_fg(), "\x1b[38;2;107;158;190m");
+        assert_eq!(ANSI_RESET, "\x1b[0m");
+    }
+
+    #[test]
+    fn ansi256() {
+        assert_eq!(EncodedColor::RED.to_ansi256(), 196);
+        assert_eq!(EncodedColor::WHITE.to_ansi256(), 231);
+        assert_eq!(EncodedColor::BLACK.to_ansi256(), 16);
+
+        // grays land in the grayscale ramp
+        let gray = EncodedColor::new(128, 128, 128, 255).to_ansi256();
+        assert_eq!(gray, 244);
+        assert!((232..=255).contains(&EncodedColor::new(60, 61, 60, 255).to_ansi256()));
+
+        // every palette color maps back to itself
+        for index in 16..=255 {
+            let color = EncodedColor::from_ansi256(index);
+            assert_eq!(EncodedColor::from_ansi256(color.to_ansi256()), color);
+        }
+
+        assert_eq!(EncodedColor::from_ansi256(9), EncodedColor::RED);
+        assert_eq!(EncodedColor::from_ansi256(67), EncodedColor::new(95, 135, 175, 255));
+    }
+}
@@ -0,0 +1,561 @@
+use crate::{EncodedColor, LinearColor};
+
+/// What a color is multiplied by before it goes into a [BlendOp], like the blend factors in a
+/// GPU's blend state. Used by [blend_with](LinearColor::blend_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendFactor {
+    /// `0`
+    Zero,
+    /// `1`
+    One,
+    /// The source's channels.
+    SrcColor,
+    /// One minus the source's channels.
+    OneMinusSrcColor,
+    /// The destination's channels.
+    DstColor,
+    /// One minus the destination's channels.
+    OneMinusDstColor,
+    /// The source's alpha.
+    SrcAlpha,
+    /// One minus the source's alpha.
+    OneMinusSrcAlpha,
+    /// The destination's alpha.
+    DstAlpha,
+    /// One minus the destination's alpha.
+    OneMinusDstAlpha,
+}
+
+/// How the weighted source and destination are combined, like the blend operations in a GPU's
+/// blend state. Used by [blend_with](LinearColor::blend_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendOp {
+    /// `src * src_factor + dst * dst_factor`
+    Add,
+    /// `src * src_factor - dst * dst_factor`
+    Subtract,
+    /// `dst * dst_factor - src * src_factor`
+    ReverseSubtract,
+    /// `min(src, dst)`. Like on GPUs, the factors are ignored.
+    Min,
+    /// `max(src, dst)`. Like on GPUs, the factors are ignored.
+    Max,
+}
+
+/// How the source and destination colors combine where they overlap, for
+/// [composite](LinearColor::composite). These are the separable blend modes from CSS and SVG's
+/// `mix-blend-mode`. Where only one of the colors is present, that color is used as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// The source covers the destination.
+    #[default]
+    Normal,
+    /// `src + dst`, for light effects. This isn't clamped, so it can go past white.
+    Add,
+    /// `src * dst`, which can only get darker.
+    Multiply,
+    /// `1 - (1 - src) * (1 - dst)`, which can only get lighter.
+    Screen,
+    /// The darker of the two, per channel.
+    Darken,
+    /// The lighter of the two, per channel.
+    Lighten,
+}
+
+impl BlendMode {
+    fn blend(self, src: f32, dst: f32) -> f32 {
+        match self {
+            BlendMode::Normal => src,
+            BlendMode::Add => src + dst,
+            BlendMode::Multiply => src * dst,
+            BlendMode::Screen => 1.0 - (1.0 - src) * (1.0 - dst),
+            BlendMode::Darken => src.min(dst),
+            BlendMode::Lighten => src.max(dst),
+        }
+    }
+}
+
+/// How the source and destination's coverage combine, for
+/// [composite](LinearColor::composite). These are the Porter-Duff operators, like Canvas's
+/// `globalCompositeOperation`: each one says how much of the source and of the destination
+/// show up in the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AlphaMode {
+    /// The source on top of the destination. This is the usual one.
+    #[default]
+    Over,
+    /// Only the source, ignoring the destination.
+    Source,
+    /// Only the destination, ignoring the source.
+    Destination,
+    /// The source, but only where the destination is.
+    In,
+    /// The source, but only where the destination isn't.
+    Out,
+    /// The source on top of the destination, but only where the destination is.
+    Atop,
+    /// The source and the destination, but not where they overlap.
+    Xor,
+    /// The source and the destination added together, with alpha clamped to 1.0. Where both
+    /// are opaque, this adds their light.
+    Plus,
+}
+
+impl AlphaMode {
+    /// The Porter-Duff weights for the source and the destination.
+    fn weights(self, src_a: f32, dst_a: f32) -> (f32, f32) {
+        match self {
+            AlphaMode::Over => (1.0, 1.0 - src_a),
+            AlphaMode::Source => (1.0, 0.0),
+            AlphaMode::Destination => (0.0, 1.0),
+            AlphaMode::In => (dst_a, 0.0),
+            AlphaMode::Out => (1.0 - dst_a, 0.0),
+            AlphaMode::Atop => (dst_a, 1.0 - src_a),
+            AlphaMode::Xor => (1.0 - dst_a, 1.0 - src_a),
+            AlphaMode::Plus => (1.0, 1.0),
+        }
+    }
+}
+
+impl BlendFactor {
+    fn weights(self, src: LinearColor, dst: LinearColor) -> [f32; 4] {
+        match self {
+            BlendFactor::Zero => [0.0; 4],
+            BlendFactor::One => [1.0; 4],
+            BlendFactor::SrcColor => [src.r, src.g, src.b, src.a],
+            BlendFactor::OneMinusSrcColor => [1.0 - src.r, 1.0 - src.g, 1.0 - src.b, 1.0 - src.a],
+            BlendFactor::DstColor => [dst.r, dst.g, dst.b, dst.a],
+            BlendFactor::OneMinusDstColor => [1.0 - dst.r, 1.0 - dst.g, 1.0 - dst.b, 1.0 - dst.a],
+            BlendFactor::SrcAlpha => [src.a; 4],
+            BlendFactor::OneMinusSrcAlpha => [1.0 - src.a; 4],
+            BlendFactor::DstAlpha => [dst.a; 4],
+            BlendFactor::OneMinusDstAlpha => [1.0 - dst.a; 4],
+        }
+    }
+}
+
+impl LinearColor {
+    /// Blends this color (the source) onto `dst` with the fixed-function blend equation GPUs
+    /// use, so CPU-side previews can match a pipeline's blend state exactly. The same factors
+    /// are used for alpha as for the color channels, and the result isn't clamped.
+    ///
+    /// This works on whatever values it's given. A GPU blending into an sRGB render target
+    /// blends in linear space, which is what you get by using [LinearColor] as usual. To match a
+    /// non-sRGB (`Unorm`) target instead, build the colors from the encoded values, with
+    /// [to_encoded_f32s](EncodedColor::to_encoded_f32s).
+    ///
+    /// Regular alpha blending is `SrcAlpha`, `OneMinusSrcAlpha`, and `Add`.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn blend_with(
+        self,
+        dst: LinearColor,
+        src_factor: BlendFactor,
+        dst_factor: BlendFactor,
+        op: BlendOp,
+    ) -> LinearColor {
+        let s = [self.r, self.g, self.b, self.a];
+        let d = [dst.r, dst.g, dst.b, dst.a];
+        let sf = src_factor.weights(self, dst);
+        let df = dst_factor.weights(self, dst);
+
+        let [r, g, b, a] = core::array::from_fn(|i| match op {
+            BlendOp::Add => s[i] * sf[i] + d[i] * df[i],
+            BlendOp::Subtract => s[i] * sf[i] - d[i] * df[i],
+            BlendOp::ReverseSubtract => d[i] * df[i] - s[i] * sf[i],
+            BlendOp::Min => s[i].min(d[i]),
+            BlendOp::Max => s[i].max(d[i]),
+        });
+
+        LinearColor::new(r, g, b, a)
+    }
+
+    /// Composites this color (the source) onto `dst`, with separate choices for how the colors
+    /// combine and how the alpha combines, like the compositing in SVG, Canvas, and most image
+    /// editors. For example, [BlendMode::Add] with [AlphaMode::Over] adds light where the two
+    /// overlap, but otherwise layers like normal.
+    ///
+    /// Both colors, and the output, have straight (not premultiplied) alpha. This follows the
+    /// W3C compositing spec: where the colors overlap, they're blended with `color_op`, and
+    /// then the result is composited with the Porter-Duff operator `alpha_mode`.
+    ///
+    /// The defaults, [BlendMode::Normal] and [AlphaMode::Over], are the plain "over" of
+    /// [blend_over](EncodedColor::blend_over). If nothing is left, the output is transparent
+    /// black.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn composite(self, dst: LinearColor, color_op: BlendMode, alpha_mode: AlphaMode) -> LinearColor {
+        let (src_weight, dst_weight) = alpha_mode.weights(self.a, dst.a);
+        let src_weight = src_weight * self.a;
+        let dst_weight = dst_weight * dst.a;
+
+        // only `Plus` can go over 1.0. Dividing by the clamped alpha keeps the light it added.
+        let a = (src_weight + dst_weight).min(1.0);
+        if a <= 0.0 {
+            return LinearColor::default();
+        }
+
+        let channel = |s: f32, d: f32| {
+            // the source's color, blended with the destination where the destination is
+            let s = (1.0 - dst.a) * s + dst.a * color_op.blend(s, d);
+            (s * src_weight + d * dst_weight) / a
+        };
+
+        LinearColor::new(
+            channel(self.r, dst.r),
+            channel(self.g, dst.g),
+            channel(self.b, dst.b),
+            a,
+        )
+    }
+
+    /// Composites this color over `dst` (Porter-Duff "over", with straight alpha), and returns
+    /// the result along with its coverage, which is the result's alpha. This is for software
+    /// renderers which accumulate coverage as they layer fragments, and stop once a pixel is
+    /// covered.
+    ///
+    /// ```
+    /// # use smol_rgb::LinearColor;
+    /// let mut pixel = LinearColor::new(0.0, 0.0, 0.0, 0.0);
+    /// for fragment in [LinearColor::new(1.0, 0.0, 0.0, 0.5); 3] {
+    ///     let (color, coverage) = fragment.over_with_coverage(pixel);
+    ///     pixel = color;
+    ///     if coverage > 0.8 {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(pixel.a, 0.875);
+    /// ```
+    pub fn over_with_coverage(self, dst: LinearColor) -> (LinearColor, f32) {
+        let output = over_linear(self, dst);
+
+        (output, output.a)
+    }
+}
+
+impl EncodedColor {
+    /// Multiplies this color by `tint`, like a multiply blend or a "colorize" effect. Multiplying
+    /// by white changes nothing, and multiplying by black gives black.
+    ///
+    /// This is done in linear space, which is how light really combines. Multiplying the u8s
+    /// directly, which is common, gives midtones which are too light. Alpha is multiplied too, so
+    /// an opaque tint keeps this color's alpha.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn multiply(self, tint: EncodedColor) -> EncodedColor {
+        let (a, b) = (self.to_linear(), tint.to_linear());
+
+        LinearColor::new(a.r * b.r, a.g * b.g, a.b * b.b, a.a * b.a).to_encoded_space()
+    }
+
+    /// Screens this color with `tint`, the opposite of [multiply](Self::multiply): each channel
+    /// is `1 - (1 - a) * (1 - b)`, so it can only get lighter. Screening with black changes
+    /// nothing, and screening with white gives white.
+    ///
+    /// Like [multiply](Self::multiply), this is done in linear space, and alpha is multiplied,
+    /// so an opaque tint keeps this color's alpha.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn screen(self, tint: EncodedColor) -> EncodedColor {
+        let (a, b) = (self.to_linear(), tint.to_linear());
+        let screen = |a: f32, b: f32| 1.0 - (1.0 - a) * (1.0 - b);
+
+        LinearColor::new(screen(a.r, b.r), screen(a.g, b.g), screen(a.b, b.b), a.a * b.a).to_encoded_space()
+    }
+
+    /// Composites this color over `background`, the normal way layers stack in an image editor
+    /// (Porter-Duff "over"). Both colors have straight (not premultiplied) alpha, and so does
+    /// the output.
+    ///
+    /// This is done in linear space, so semi-transparent edges don't get the dark fringes you see
+    /// when blending the u8s directly.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn blend_over(self, background: EncodedColor) -> EncodedColor {
+        over(self.to_linear(), background.to_linear())
+    }
+
+    /// Composites this color over `background` as if the background were fully opaque, so the
+    /// result is always opaque too. This is what you want before writing to a format without
+    /// alpha, like JPEG, or to a terminal.
+    ///
+    /// Like [blend_over](Self::blend_over), this is done in linear space. `background`'s alpha
+    /// is ignored.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn flatten_onto(self, background: EncodedColor) -> EncodedColor {
+        self.blend_over(background.with_a(255))
+    }
+
+    /// Composites every pixel of `foreground` over the matching pixel of `background`, writing
+    /// the results into `output`. This gives the same results as calling
+    /// [blend_over](Self::blend_over) on each pixel, for layering whole images.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the three slices aren't all the same length.
+    pub fn composite_slices(foreground: &[EncodedColor], background: &[EncodedColor], output: &mut [EncodedColor]) {
+        assert_eq!(
+            foreground.len(),
+            background.len(),
+            "foreground and background must be the same length"
+        );
+        assert_eq!(
+            foreground.len(),
+            output.len(),
+            "output must be the same length as the inputs"
+        );
+
+        for ((out, fg), bg) in output.iter_mut().zip(foreground).zip(background) {
+            *out = over(fg.to_linear(), bg.to_linear());
+        }
+    }
+
+    /// A stochastic "dissolve" blend: returns `other` with probability `t`, and `self` otherwise.
+    /// Over many pixels (or frames), this looks like a grainy crossfade, which is handy for
+    /// particles and transitions.
+    ///
+    /// A `t` of 0.0 or below always gives `self`, and 1.0 or above always gives `other`. With a
+    /// seeded `rng`, the results are deterministic.
+    #[cfg(feature = "rand")]
+    pub fn dissolve<R: rand::Rng + ?Sized>(self, other: EncodedColor, t: f32, rng: &mut R) -> EncodedColor {
+        if rng.gen::<f32>() < t { other } else { self }
+    }
+}
+
+/// The "over" operator on straight alpha colors, for [EncodedColor::blend_over].
+#[inline]
+fn over(fg: LinearColor, bg: LinearColor) -> EncodedColor {
+    let output = over_linear(fg, bg);
+    output.to_encoded_space().with_a(output.alpha_u8())
+}
+
+/// Porter-Duff "over" with straight alpha, in linear space. Transparent black if nothing's left.
+fn over_linear(fg: LinearColor, bg: LinearColor) -> LinearColor {
+    let bg_weight = bg.a * (1.0 - fg.a);
+    let a = fg.a + bg_weight;
+    if a <= 0.0 {
+        return LinearColor::default();
+    }
+
+    let channel = |f: f32, b: f32| (f * fg.a + b * bg_weight) / a;
+    LinearColor::new(channel(fg.r, bg.r), channel(fg.g, bg.g), channel(fg.b, bg.b), a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::close;
+
+    #[test]
+    fn blend_with() {
+        let src = LinearColor::new(0.8, 0.2, 0.1, 0.25);
+        let dst = LinearColor::new(0.1, 0.4, 0.9, 1.0);
+
+        // regular alpha blending
+        let over = src.blend_with(dst, BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha, BlendOp::Add);
+        let expected = LinearColor::new(
+            0.8 * 0.25 + 0.1 * 0.75,
+            0.2 * 0.25 + 0.4 * 0.75,
+            0.1 * 0.25 + 0.9 * 0.75,
+            0.25 * 0.25 + 0.75,
+        );
+        assert_eq!(over, expected);
+
+        // additive
+        let add = src.blend_with(dst, BlendFactor::One, BlendFactor::One, BlendOp::Add);
+        assert_eq!(add, LinearColor::new(0.8 + 0.1, 0.2 + 0.4, 0.1 + 0.9, 1.25));
+
+        // multiply
+        let multiply = src.blend_with(dst, BlendFactor::DstColor, BlendFactor::Zero, BlendOp::Add);
+        assert_eq!(multiply, LinearColor::new(0.8 * 0.1, 0.2 * 0.4, 0.1 * 0.9, 0.25));
+
+        let min = src.blend_with(dst, BlendFactor::Zero, BlendFactor::Zero, BlendOp::Min);
+        assert_eq!(min, LinearColor::new(0.1, 0.2, 0.1, 0.25));
+        let subtract = src.blend_with(dst, BlendFactor::One, BlendFactor::One, BlendOp::ReverseSubtract);
+        assert_eq!(subtract, LinearColor::new(0.1 - 0.8, 0.4 - 0.2, 0.9 - 0.1, 1.0 - 0.25));
+    }
+
+    #[test]
+    fn multiply_and_screen() {
+        let color = EncodedColor::new(107, 158, 190, 200);
+
+        assert_eq!(color.multiply(EncodedColor::WHITE), color);
+        assert_eq!(color.multiply(EncodedColor::BLACK), EncodedColor::BLACK.with_a(200));
+        assert_eq!(color.screen(EncodedColor::BLACK), color);
+        assert_eq!(color.screen(EncodedColor::WHITE), EncodedColor::WHITE.with_a(200));
+
+        // in linear space, a 50% gray multiplied by itself is darker than multiplying the bytes
+        let gray = EncodedColor::new(128, 128, 128, 255);
+        let multiplied = gray.multiply(gray);
+        let l = crate::encoded_to_linear(128);
+        assert_eq!(
+            multiplied,
+            LinearColor::new(l * l, l * l, l * l, 1.0).to_encoded_space()
+        );
+        assert!(multiplied.r < (128 * 128 / 255) as u8);
+
+        // and alpha multiplies
+        assert_eq!(EncodedColor::WHITE.multiply(EncodedColor::WHITE.with_a(128)).a, 128);
+        assert_eq!(color.multiply(EncodedColor::RED), EncodedColor::new(107, 0, 0, 200));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dissolve() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+        let (a, b) = (EncodedColor::RED, EncodedColor::BLUE);
+
+        for t in [0.1, 0.5, 0.9] {
+            let picked_b = (0..10_000).filter(|_| a.dissolve(b, t, &mut rng) == b).count();
+            let ratio = picked_b as f32 / 10_000.0;
+            assert!((ratio - t).abs() < 0.02, "{} vs {}", ratio, t);
+        }
+
+        assert!((0..1000).all(|_| a.dissolve(b, 0.0, &mut rng) == a));
+        assert!((0..1000).all(|_| a.dissolve(b, 1.0, &mut rng) == b));
+
+        // and a seeded rng gives the same dissolve every time
+        let run = || {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+            core::array::from_fn::<_, 64, _>(|_| a.dissolve(b, 0.5, &mut rng))
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn composite() {
+        let src = LinearColor::new(0.8, 0.2, 0.1, 0.5);
+        let dst = LinearColor::new(0.1, 0.4, 0.6, 1.0);
+
+        // the defaults are plain over
+        let sky = EncodedColor::new(107, 158, 190, 128);
+        let rust = EncodedColor::new(183, 65, 14, 200);
+        for (s, d) in [(sky, rust), (rust, sky), (sky, rust.with_a(255)), (sky.with_a(0), rust)] {
+            let composited = s
+                .to_linear()
+                .composite(d.to_linear(), BlendMode::default(), AlphaMode::default());
+            assert_eq!(
+                composited.to_encoded_space().with_a(composited.alpha_u8()),
+                s.blend_over(d)
+            );
+        }
+
+        // additive color with over alpha: half of the source's light is added to the
+        // destination, which keeps its own
+        let added = src.composite(dst, BlendMode::Add, AlphaMode::Over);
+        assert!(
+            close(added, LinearColor::new(0.1 + 0.4, 0.4 + 0.1, 0.6 + 0.05, 1.0)),
+            "{:?}",
+            added
+        );
+
+        // on a transparent destination, the blend mode doesn't matter
+        let clear = LinearColor::default();
+        for mode in [BlendMode::Add, BlendMode::Multiply, BlendMode::Darken] {
+            assert!(close(src.composite(clear, mode, AlphaMode::Over), src));
+        }
+
+        // and the other operators
+        assert!(close(src.composite(dst, BlendMode::Normal, AlphaMode::Source), src));
+        assert!(close(
+            src.composite(dst, BlendMode::Normal, AlphaMode::Destination),
+            dst
+        ));
+        assert!(close(src.composite(dst, BlendMode::Normal, AlphaMode::In), src));
+        assert_eq!(src.composite(dst, BlendMode::Normal, AlphaMode::Out), clear);
+        assert!(close(
+            src.composite(dst, BlendMode::Multiply, AlphaMode::Atop),
+            LinearColor::new(0.5 * 0.08 + 0.05, 0.5 * 0.08 + 0.2, 0.5 * 0.06 + 0.3, 1.0)
+        ));
+        assert_eq!(src.composite(src, BlendMode::Normal, AlphaMode::Xor).a, 0.5);
+        assert!(close(
+            src.composite(dst, BlendMode::Normal, AlphaMode::Plus),
+            LinearColor::new(0.5, 0.5, 0.65, 1.0)
+        ));
+    }
+
+    #[test]
+    fn blend_over() {
+        let red = EncodedColor::RED;
+        let blue = EncodedColor::BLUE;
+
+        assert_eq!(red.blend_over(blue), red);
+        assert_eq!(red.with_a(0).blend_over(blue), blue);
+        assert_eq!(EncodedColor::CLEAR.blend_over(EncodedColor::CLEAR), EncodedColor::CLEAR);
+
+        // half red over blue is half of each in linear space, which is lighter than averaging
+        // the u8s
+        let half = red.with_a(128).blend_over(blue);
+        assert_eq!(half.a, 255);
+        assert!(half.r > 128 && half.b > 128, "{:?}", half);
+        assert!(half.r.abs_diff(half.b) <= 1);
+
+        // over a transparent background, the foreground is unchanged
+        let sky = EncodedColor::new(107, 158, 190, 100);
+        assert_eq!(sky.blend_over(EncodedColor::CLEAR), sky);
+
+        // two half transparent layers make a three quarters opaque one
+        assert_eq!(sky.with_a(128).blend_over(sky.with_a(128)), sky.with_a(192));
+    }
+
+    #[test]
+    fn flatten_onto() {
+        let pink = EncodedColor::RED.with_a(128).flatten_onto(EncodedColor::WHITE);
+        assert_eq!(pink, EncodedColor::new(255, 187, 187, 255));
+
+        // the background's alpha doesn't matter, and the foreground's is gone
+        assert_eq!(
+            EncodedColor::RED
+                .with_a(128)
+                .flatten_onto(EncodedColor::WHITE.with_a(0)),
+            pink
+        );
+        assert_eq!(
+            EncodedColor::CLEAR.flatten_onto(EncodedColor::TEAL.with_a(3)),
+            EncodedColor::TEAL
+        );
+        assert_eq!(EncodedColor::RED.flatten_onto(EncodedColor::BLUE), EncodedColor::RED);
+    }
+
+    #[test]
+    fn over_with_coverage() {
+        let src = LinearColor::new(0.8, 0.2, 0.1, 0.25);
+        let dst = LinearColor::new(0.1, 0.4, 0.9, 0.5);
+
+        let (color, coverage) = src.over_with_coverage(dst);
+        assert_eq!(coverage, color.a);
+        assert_eq!(coverage, 0.625);
+
+        // the same "over" as blend_over
+        let (fg, bg) = (
+            EncodedColor::new(200, 30, 30, 100),
+            EncodedColor::new(107, 158, 190, 77),
+        );
+        let (color, coverage) = fg.to_linear().over_with_coverage(bg.to_linear());
+        assert_eq!(color.to_encoded_space().with_a(color.alpha_u8()), fg.blend_over(bg));
+        assert_eq!(coverage, color.a);
+
+        let (color, coverage) = LinearColor::default().over_with_coverage(LinearColor::default());
+        assert_eq!((color, coverage), (LinearColor::default(), 0.0));
+        assert_eq!(src.over_with_coverage(LinearColor { a: 1.0, ..dst }).1, 1.0);
+    }
+
+    #[test]
+    fn composite_slices() {
+        let foreground: [EncodedColor; 16] =
+            core::array::from_fn(|i| EncodedColor::new(i as u8 * 16, 200, 30, (i * 17) as u8));
+        let background: [EncodedColor; 16] =
+            core::array::from_fn(|i| EncodedColor::new(10, i as u8 * 8, 240, 255 - (i * 9) as u8));
+
+        let mut output = [EncodedColor::CLEAR; 16];
+        EncodedColor::composite_slices(&foreground, &background, &mut output);
+
+        for i in 0..16 {
+            assert_eq!(output[i], foreground[i].blend_over(background[i]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_slices_lengths() {
+        let mut output = [EncodedColor::CLEAR; 2];
+        EncodedColor::composite_slices(&[EncodedColor::RED; 2], &[EncodedColor::BLUE; 3], &mut output);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{EncodedColor, LinearColor};
+
+/// A kind of dichromatic color blindness, for [simulate_color_blindness].
+///
+/// [simulate_color_blindness]: EncodedColor::simulate_color_blindness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorBlindness {
+    /// No working long-wavelength (red) cones. Reds look dark, and are confused with greens.
+    Protanopia,
+
+    /// No working medium-wavelength (green) cones. The most common kind, where reds and greens
+    /// are confused.
+    Deuteranopia,
+
+    /// No working short-wavelength (blue) cones. Blues are confused with greens, and yellows
+    /// with pinks. This one is rare.
+    Tritanopia,
+}
+
+impl ColorBlindness {
+    /// Rebuilds the missing cone's response out of the other two, which is how the world looks
+    /// to someone without it. This is the single plane method from Viénot, Brettel, and Mollon,
+    /// using their matrices for protanopia and deuteranopia.
+    ///
+    /// The tritanopia matrix which usually goes along with those turns saturated reds yellow,
+    /// which tritanopes don't see. Instead, our plane goes through white and the sRGB red
+    /// primary, so both of those are unchanged.
+    fn project(self, (l, m, s): (f32, f32, f32)) -> (f32, f32, f32) {
+        match self {
+            ColorBlindness::Protanopia => (2.02344 * m - 2.52581 * s, m, s),
+            ColorBlindness::Deuteranopia => (l, 0.494207 * l + 1.24827 * s, s),
+            ColorBlindness::Tritanopia => (l, m, -0.0122450 * l + 0.0720345 * m),
+        }
+    }
+}
+
+impl LinearColor {
+    /// Simulates how this color looks to someone with the given kind of color blindness. The
+    /// simulation is done in [LMS](Self::to_lms) (cone response) space, so it has to start from linear. Grays
+    /// are unchanged, and alpha is kept.
+    ///
+    /// The output can be a little outside of 0.0 to 1.0.
+    pub fn simulate_color_blindness(self, kind: ColorBlindness) -> LinearColor {
+        LinearColor::from_lms(kind.project(self.to_lms()), self.a)
+    }
+
+    /// Shifts this color so that it's easier to tell apart from others for someone with the
+    /// given kind of color blindness, often called daltonization.
+    ///
+    /// We find the difference between this color and how it's seen (with
+    /// [simulate_color_blindness](Self::simulate_color_blindness)), and move that difference
+    /// into the channels which *are* seen: for protanopia and deuteranopia, the lost red-green
+    /// difference goes into green and blue, and for tritanopia, the lost blue-yellow difference
+    /// goes into red and green. `strength` scales how much is moved, where 0.0 changes nothing
+    /// and 1.0 is the usual amount. Alpha is kept.
+    ///
+    /// The output can be outside of 0.0 to 1.0.
+    pub fn daltonize(self, kind: ColorBlindness, strength: f32) -> LinearColor {
+        let seen = self.simulate_color_blindness(kind);
+        let (r, g, b) = (self.r - seen.r, self.g - seen.g, self.b - seen.b);
+
+        let (r, g, b) = match kind {
+            ColorBlindness::Protanopia | ColorBlindness::Deuteranopia => (0.0, 0.7 * r + g, 0.7 * r + b),
+            ColorBlindness::Tritanopia => (r + 0.7 * b, g + 0.7 * b, 0.0),
+        };
+
+        LinearColor {
+            r: self.r + r * strength,
+            g: self.g + g * strength,
+            b: self.b + b * strength,
+            a: self.a,
+        }
+    }
+}
+
+impl EncodedColor {
+    /// Simulates how this color looks to someone with the given kind of color blindness, for
+    /// checking that a palette still works for them. Grays are unchanged, and alpha is kept.
+    ///
+    /// This goes through linear space; see [LinearColor::simulate_color_blindness].
+    pub fn simulate_color_blindness(self, kind: ColorBlindness) -> EncodedColor {
+        let simulated = self.to_linear().simulate_color_blindness(kind);
+
+        LinearColor {
+            r: simulated.r.clamp(0.0, 1.0),
+            g: simulated.g.clamp(0.0, 1.0),
+            b: simulated.b.clamp(0.0, 1.0),
+            ..simulated
+        }
+        .to_encoded_space()
+        .with_a(self.a)
+    }
+
+    /// Shifts this color so that it's easier to tell apart from others for someone with the
+    /// given kind of color blindness. A `strength` of 0.0 changes nothing, and 1.0 is the usual
+    /// amount. Alpha is kept.
+    ///
+    /// This goes through linear space; see [LinearColor::daltonize].
+    pub fn daltonize(self, kind: ColorBlindness, strength: f32) -> EncodedColor {
+        let corrected = self.to_linear().daltonize(kind, strength);
+
+        LinearColor {
+            r: corrected.r.clamp(0.0, 1.0),
+            g: corrected.g.clamp(0.0, 1.0),
+            b: corrected.b.clamp(0.0, 1.0),
+            ..corrected
+        }
+        .to_encoded_space()
+        .with_a(self.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [ColorBlindness; 3] = [
+        ColorBlindness::Protanopia,
+        ColorBlindness::Deuteranopia,
+        ColorBlindness::Tritanopia,
+    ];
+
+    #[test]
+    fn grays_stay_gray() {
+        for kind in KINDS {
+            for v in [0, 30, 128, 200, 255] {
+                let gray = EncodedColor::new(v, v, v, 77);
+                let simulated = gray.simulate_color_blindness(kind);
+
+                for (a, b) in [(gray.r, simulated.r), (gray.g, simulated.g), (gray.b, simulated.b)] {
+                    assert!(a.abs_diff(b) <= 1, "{:?} {:?} {:?}", kind, gray, simulated);
+                }
+                assert_eq!(simulated.a, 77);
+            }
+        }
+    }
+
+    #[test]
+    fn confuses_the_right_colors() {
+        let red = EncodedColor::new(220, 40, 40, 255);
+        let green = EncodedColor::new(60, 160, 60, 255);
+        let blue = EncodedColor::new(40, 60, 220, 255);
+
+        for kind in [ColorBlindness::Protanopia, ColorBlindness::Deuteranopia] {
+            let sim_red = red.simulate_color_blindness(kind);
+            let sim_green = green.simulate_color_blindness(kind);
+
+            assert!(sim_red.delta_e(red) > 0.05, "{:?}", kind);
+
+            // the difference along the red-green axis is gone
+            let red_green = |a: EncodedColor, b: EncodedColor| (a.to_oklab().a - b.to_oklab().a).abs();
+            assert!(red_green(red, green) > 0.2);
+            assert!(red_green(sim_red, sim_green) < 0.02, "{:?}", kind);
+        }
+
+        // tritanopes see reds fine, but not blues
+        let tritan = ColorBlindness::Tritanopia;
+        assert!(
+            red.simulate_color_blindness(tritan).delta_e(red) < blue.simulate_color_blindness(tritan).delta_e(blue)
+        );
+        assert!(blue.simulate_color_blindness(tritan).delta_e(blue) > 0.05);
+
+        // and they confuse yellows with pinks
+        let yellow = EncodedColor::new(230, 220, 60, 255);
+        let pink = EncodedColor::new(240, 170, 200, 255);
+        let sim_yellow = yellow.simulate_color_blindness(tritan);
+        assert!(sim_yellow.delta_e(pink.simulate_color_blindness(tritan)) < yellow.delta_e(pink) / 2.0);
+    }
+
+    #[test]
+    fn daltonize() {
+        let red = EncodedColor::new(220, 40, 40, 255);
+        let green = EncodedColor::new(60, 160, 60, 128);
+        let blue = EncodedColor::new(40, 60, 220, 255);
+
+        for kind in KINDS {
+            for color in [red, green, blue, EncodedColor::new(107, 158, 190, 255)] {
+                assert_eq!(color.daltonize(kind, 0.0), color);
+            }
+        }
+
+        // grays don't have anything to correct
+        let gray = EncodedColor::new(128, 128, 128, 255);
+        assert!(gray.daltonize(ColorBlindness::Deuteranopia, 1.0).delta_e(gray) < 0.01);
+
+        // after correcting, the colors are easier to tell apart for the people they're for
+        for (kind, a, b) in [
+            (ColorBlindness::Protanopia, red, green),
+            (ColorBlindness::Deuteranopia, red, green),
+            (ColorBlindness::Tritanopia, blue, green),
+        ] {
+            let seen = |c: EncodedColor, strength: f32| c.daltonize(kind, strength).simulate_color_blindness(kind);
+
+            let before = seen(a, 0.0).delta_e(seen(b, 0.0));
+            let after = seen(a, 1.0).delta_e(seen(b, 1.0));
+            assert!(a.daltonize(kind, 1.0).delta_e(a) > 0.02, "{:?}", kind);
+            assert!(after > before, "{:?} {} {}", kind, before, after);
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{
+    EncodedColor, LinearColor, Oklab, Oklch,
+    hsluv::{EPSILON, FROM_XYZ, KAPPA, TO_XYZ, wrap_hue},
+    math,
+};
+
+/// Converts a color into the `Target` color space, so every conversion in the crate goes
+/// through one method:
+///
+/// ```
+/// # use smol_rgb::{EncodedColor, Lab, Oklch};
+/// let sky = EncodedColor::new(107, 158, 190, 255);
+/// let lch = sky.convert::<Oklch>();
+/// let lab = sky.convert::<Lab>();
+/// # let _ = (lch, lab);
+/// ```
+///
+/// Every color type in this crate has an inherent `convert` method which calls this, so you
+/// don't need the trait in scope for them. Implement this to plug in your own color spaces,
+/// which then work with `convert` too:
+///
+/// ```
+/// # use smol_rgb::{ConvertColor, EncodedColor};
+/// struct Luma(u8);
+///
+/// impl ConvertColor<Luma> for EncodedColor {
+///     fn convert(self) -> Luma {
+///         Luma(self.grayscale().r)
+///     }
+/// }
+///
+/// assert_eq!(EncodedColor::WHITE.convert::<Luma>().0, 255);
+/// ```
+///
+/// Spaces without alpha drop it, and converting from them gives an opaque color.
+pub trait ConvertColor<Target> {
+    /// Converts this color into `Target`.
+    fn convert(self) -> Target;
+}
+
+/// A color in HSL, like CSS's `hsl()`. This works on the encoded values, so it isn't
+/// perceptually uniform. For that, see [Oklch].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Hsl {
+    /// The hue, in degrees from 0.0 to 360.0. For grays, this is 0.0.
+    pub h: f32,
+
+    /// The saturation, from 0.0 to 1.0.
+    pub s: f32,
+
+    /// The lightness, from 0.0 (black) to 1.0 (white).
+    pub l: f32,
+}
+
+/// A color in HSV (sometimes called HSB), like the pickers in most image editors. Like [Hsl],
+/// this works on the encoded values.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Hsv {
+    /// The hue, in degrees from 0.0 to 360.0. For grays, this is 0.0.
+    pub h: f32,
+
+    /// The saturation, from 0.0 to 1.0.
+    pub s: f32,
+
+    /// The value, from 0.0 (black) to 1.0.
+    pub v: f32,
+}
+
+/// A color in CIE XYZ, with the D65 white point. `y` is the luminance, so white is about
+/// `(0.95, 1.0, 1.09)`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Xyz {
+    /// The X component.
+    pub x: f32,
+
+    /// The Y component, which is the luminance.
+    pub y: f32,
+
+    /// The Z component.
+    pub z: f32,
+}
+
+/// A color in CIELAB (L\*a\*b\*), with the D65 white point. This is the older perceptual space
+/// many tools and standards still use. For new work, [Oklab] is more uniform.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Lab {
+    /// The lightness, from 0.0 (black) to 100.0 (white).
+    pub l: f32,
+
+    /// How green (negative) or red (positive) the color is.
+    pub a: f32,
+
+    /// How blue (negative) or yellow (positive) the color is.
+    pub b: f32,
+}
+
+macro_rules! inherent_convert {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $ty {
+                /// Converts this color into the `Target` color space, like `convert::<Oklab>()`.
+                /// See [ConvertColor].
+                #[inline]
+                pub fn convert<Target>(self) -> Target
+                where
+                    Self: ConvertColor<Target>,
+                {
+                    ConvertColor::convert(self)
+                }
+            }
+        )*
+    };
+}
+
+inherent_convert!(EncodedColor, LinearColor, Oklab, Oklch, Hsl, Hsv, Xyz, Lab);
+
+impl ConvertColor<LinearColor> for EncodedColor {
+    fn convert(self) -> LinearColor {
+        self.to_linear()
+    }
+}
+
+impl ConvertColor<EncodedColor> for LinearColor {
+    fn convert(self) -> EncodedColor {
+        self.to_encoded_space()
+    }
+}
+
+impl ConvertColor<Hsl> for EncodedColor {
+    fn convert(self) -> Hsl {
+        let (h, s, l) = self.to_hsl_f32();
+
+        Hsl { h, s, l }
+    }
+}
+
+impl ConvertColor<EncodedColor> for Hsl {
+    fn convert(self) -> EncodedColor {
+        EncodedColor::from_hsl_f32(wrap_hue(self.h), self.s.clamp(0.0, 1.0), self.l.clamp(0.0, 1.0))
+    }
+}
+
+impl ConvertColor<Hsv> for EncodedColor {
+    fn convert(self) -> Hsv {
+        let Hsl { h, s, l } = self.convert();
+        let v = l + s * l.min(1.0 - l);
+        let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+
+        Hsv { h, s, v }
+    }
+}
+
+impl ConvertColor<EncodedColor> for Hsv {
+    fn convert(self) -> EncodedColor {
+        let Hsv { h, s, v } = self;
+        let l = v * (1.0 - s / 2.0);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (v - l) / l.min(1.0 - l)
+        };
+
+        Hsl { h, s, l }.convert()
+    }
+}
+
+impl ConvertColor<Xyz> for LinearColor {
+    fn convert(self) -> Xyz {
+        let [x, y, z] = TO_XYZ.map(|row| row[0] * self.r + row[1] * self.g + row[2] * self.b);
+
+        Xyz { x, y, z }
+    }
+}
+
+impl ConvertColor<LinearColor> for Xyz {
+    fn convert(self) -> LinearColor {
+        let [r, g, b] = FROM_XYZ.map(|row| row[0] * self.x + row[1] * self.y + row[2] * self.z);
+
+        LinearColor::new(r, g, b, 1.0)
+    }
+}
+
+// the D65 white point, as our sRGB to XYZ matrix has it, so white is exactly L 100.
+const WHITE: [f32; 3] = [
+    TO_XYZ[0][0] + TO_XYZ[0][1] + TO_XYZ[0][2],
+    TO_XYZ[1][0] + TO_XYZ[1][1] + TO_XYZ[1][2],
+    TO_XYZ[2][0] + TO_XYZ[2][1] + TO_XYZ[2][2],
+];
+
+impl ConvertColor<Lab> for Xyz {
+    fn convert(self) -> Lab {
+        let f = |t: f32| {
+            if t > EPSILON {
+                math::cbrt(t)
+            } else {
+                (KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(self.x / WHITE[0]), f(self.y / WHITE[1]), f(self.z / WHITE[2]));
+
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+impl ConvertColor<Xyz> for Lab {
+    fn convert(self) -> Xyz {
+        let f_inverse = |f: f32| {
+            let cube = f * f * f;
+            if cube > EPSILON {
+                cube
+            } else {
+                (116.0 * f - 16.0) / KAPPA
+            }
+        };
+        let fy = (self.l + 16.0) / 116.0;
+
+        Xyz {
+            x: f_inverse(fy + self.a / 500.0) * WHITE[0],
+            y: f_inverse(fy) * WHITE[1],
+            z: f_inverse(fy - self.b / 200.0) * WHITE[2],
+        }
+    }
+}
+
+impl ConvertColor<Lab> for LinearColor {
+    fn convert(self) -> Lab {
+        self.convert::<Xyz>().convert()
+    }
+}
+
+impl ConvertColor<LinearColor> for Lab {
+    fn convert(self) -> LinearColor {
+        self.convert::<Xyz>().convert()
+    }
+}
+
+impl ConvertColor<Oklab> for LinearColor {
+    fn convert(self) -> Oklab {
+        self.to_oklab()
+    }
+}
+
+impl ConvertColor<LinearColor> for Oklab {
+    fn convert(self) -> LinearColor {
+        LinearColor::from_oklab(self, 1.0)
+    }
+}
+
+impl ConvertColor<Oklch> for LinearColor {
+    fn convert(self) -> Oklch {
+        self.to_oklch()
+    }
+}
+
+impl ConvertColor<LinearColor> for Oklch {
+    fn convert(self) -> LinearColor {
+        LinearColor::from_oklch(self, 1.0)
+    }
+}
+
+impl ConvertColor<Oklch> for Oklab {
+    fn convert(self) -> Oklch {
+        self.to_oklch()
+    }
+}
+
+impl ConvertColor<Oklab> for Oklch {
+    fn convert(self) -> Oklab {
+        self.to_oklab()
+    }
+}
+
+// the linear-space spaces and the encoded-space spaces reach each other through the other's
+// color type.
+macro_rules! convert_through {
+    ($from:ty, through $through:ty => $($ty:ty),* $(,)?) => {
+        $(
+            impl ConvertColor<$ty> for $from {
+                fn convert(self) -> $ty {
+                    self.convert::<$through>().convert()
+                }
+            }
+
+            impl ConvertColor<$from> for $ty {
+                fn convert(self) -> $from {
+                    self.convert::<$through>().convert()
+                }
+            }
+        )*
+    };
+}
+
+convert_through!(EncodedColor, through LinearColor => Xyz, Lab, Oklab, Oklch);
+convert_through!(LinearColor, through EncodedColor => Hsl, Hsv);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn through_two_spaces() {
+        let sky = EncodedColor::new(107, 158, 190, 255);
+
+        // encoded to Xyz to Lab, and back the same way
+        let lab = sky.convert::<Xyz>().convert::<Lab>();
+        assert_eq!(lab, sky.convert::<Lab>());
+        assert_eq!(lab.convert::<Xyz>().convert::<EncodedColor>(), sky);
+
+        // and Hsv to Hsl
+        let hsl = EncodedColor::from_hsv(30, 50, 80)
+            .convert::<Hsv>()
+            .convert::<EncodedColor>()
+            .convert::<Hsl>();
+        assert_eq!(hsl.convert::<EncodedColor>(), EncodedColor::from_hsv(30, 50, 80));
+
+        // Oklch through linear matches the inherent methods
+        assert_eq!(sky.convert::<Oklch>(), sky.to_oklch());
+        assert_eq!(sky.convert::<LinearColor>().convert::<Oklab>(), sky.to_oklab());
+    }
+
+    #[test]
+    fn matches_from() {
+        // alpha truncates, like the From impls and to_encoded_space, rather than rounding
+        for lin in [
+            LinearColor::new(0.2, 0.3, 0.4, 0.5),
+            LinearColor::new(0.9, 0.1, 0.0, 0.999),
+        ] {
+            assert_eq!(lin.convert::<EncodedColor>(), EncodedColor::from(lin));
+        }
+        assert_eq!(LinearColor::new(0.2, 0.3, 0.4, 0.5).convert::<EncodedColor>().a, 127);
+    }
+
+    #[test]
+    fn round_trips() {
+        let colors = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(200, 30, 30, 255),
+            EncodedColor::new(118, 118, 118, 255),
+            EncodedColor::YELLOW,
+            EncodedColor::BLACK,
+            EncodedColor::WHITE,
+        ];
+
+        for color in colors {
+            assert_eq!(color.convert::<Hsl>().convert::<EncodedColor>(), color);
+            assert_eq!(color.convert::<Hsv>().convert::<EncodedColor>(), color);
+            assert_eq!(color.convert::<Xyz>().convert::<EncodedColor>(), color);
+            assert_eq!(color.convert::<Lab>().convert::<EncodedColor>(), color);
+            assert_eq!(color.convert::<Oklab>().convert::<EncodedColor>(), color);
+            assert_eq!(color.convert::<Oklch>().convert::<EncodedColor>(), color);
+            assert_eq!(color.convert::<LinearColor>().convert::<EncodedColor>(), color);
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        let lab = EncodedColor::WHITE.convert::<Lab>();
+        assert!((lab.l - 100.0).abs() < 0.001 && lab.a.abs() < 0.001 && lab.b.abs() < 0.001);
+
+        // the usual reference value for sRGB red
+        let lab = EncodedColor::RED.convert::<Lab>();
+        assert!((lab.l - 53.24).abs() < 0.01, "{:?}", lab);
+        assert!((lab.a - 80.09).abs() < 0.05, "{:?}", lab);
+        assert!((lab.b - 67.20).abs() < 0.05, "{:?}", lab);
+
+        // (255, 128, 0), so the hue is a little over 30
+        let hsv = EncodedColor::from_hsl(30, 100, 50).convert::<Hsv>();
+        assert!((hsv.h - 30.1).abs() < 0.1 && hsv.s == 1.0 && hsv.v == 1.0, "{:?}", hsv);
+        assert_eq!(
+            Hsl {
+                h: 360.0,
+                s: 1.0,
+                l: 0.5
+            }
+            .convert::<EncodedColor>(),
+            EncodedColor::RED
+        );
+        assert_eq!(
+            EncodedColor::WHITE
+                .with_a(7)
+                .convert::<Hsv>()
+                .convert::<EncodedColor>()
+                .a,
+            255
+        );
+    }
+}
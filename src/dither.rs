@@ -0,0 +1,128 @@
+#[cfg(not(feature = "decode-lut"))]
+use crate::linear_to_encoded_f32;
+use crate::{EncodedColor, LinearColor};
+#[cfg(feature = "decode-lut")]
+use crate::{LINEAR_TO_ENCODED_LUT, linear_to_encoded_lut};
+
+impl LinearColor {
+    /// Transforms this color into the Encoded color space like
+    /// [to_encoded_space](Self::to_encoded_space), but adds a tiny bit of noise before rounding
+    /// to u8s. This breaks up the banding you otherwise see in smooth gradients, especially dark
+    /// ones.
+    ///
+    /// The noise has a triangular distribution one encoded step wide on either side, which is
+    /// the standard choice for dithering, and it's the same on r, g, and b, so grays stay gray.
+    /// It depends only on `x` and `y`, which are normally the pixel's coordinates, so a frame
+    /// dithers the same way every time. Alpha isn't dithered.
+    ///
+    /// With the `decode-lut` feature, this quantizes through the LUT, like
+    /// [linear_to_encoded](crate::linear_to_encoded).
+    pub fn to_encoded_dithered(self, x: u32, y: u32) -> EncodedColor {
+        let noise = triangular_noise(x, y);
+        let dither = |c: f32| (encoded_steps(c) + noise) as u8;
+
+        EncodedColor {
+            r: dither(self.r),
+            g: dither(self.g),
+            b: dither(self.b),
+            a: (self.a * 255.0) as u8,
+        }
+    }
+}
+
+/// The encoded value of `input`, scaled so that its whole part is the u8 that
+/// [linear_to_encoded](crate::linear_to_encoded) gives.
+#[cfg(not(feature = "decode-lut"))]
+fn encoded_steps(input: f32) -> f32 {
+    linear_to_encoded_f32(input) * 256.0
+}
+
+/// The encoded value of `input`, scaled so that its whole part is the u8 that
+/// [linear_to_encoded](crate::linear_to_encoded) gives. The step comes from the LUT, and the
+/// fraction is interpolated between its thresholds, so there's no `powf` here either.
+#[cfg(feature = "decode-lut")]
+fn encoded_steps(input: f32) -> f32 {
+    let step = linear_to_encoded_lut(input);
+    let start = match step {
+        0 => 0.0,
+        _ => LINEAR_TO_ENCODED_LUT[step as usize - 1],
+    };
+    let end = LINEAR_TO_ENCODED_LUT.get(step as usize).copied().unwrap_or(1.0);
+
+    step as f32 + ((input - start) / (end - start)).clamp(0.0, 1.0)
+}
+
+/// Returns noise from -1.0 to 1.0, with a triangular distribution, for the given coordinate.
+fn triangular_noise(x: u32, y: u32) -> f32 {
+    let first = hash(x ^ hash(y));
+    let second = hash(first);
+
+    // the top 24 bits of each give us a uniform float from 0.0 to 1.0
+    let uniform = |h: u32| (h >> 8) as f32 / (1 << 24) as f32;
+
+    uniform(first) + uniform(second) - 1.0
+}
+
+/// A small integer hash, from <https://nullprogram.com/blog/2018/07/31/>.
+fn hash(mut v: u32) -> u32 {
+    v ^= v >> 16;
+    v = v.wrapping_mul(0x7feb352d);
+    v ^= v >> 15;
+    v = v.wrapping_mul(0x846ca68b);
+    v ^= v >> 16;
+
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linear_to_encoded;
+
+    #[test]
+    fn dithered() {
+        let mut changed = 0;
+        let mut total = 0i64;
+
+        // a dark, slow gradient, which bands badly
+        for x in 0..256 {
+            let v = x as f32 / 256.0 * 0.05;
+            let color = LinearColor::new(v, v, v * 0.5, 0.5);
+
+            for y in 0..16 {
+                let dithered = color.to_encoded_dithered(x, y);
+                let plain = linear_to_encoded(v);
+
+                assert!(dithered.r.abs_diff(plain) <= 1);
+                assert_eq!(dithered.r, dithered.g);
+                assert_eq!(dithered.a, 127);
+                assert_eq!(dithered, color.to_encoded_dithered(x, y));
+
+                if dithered.r != plain {
+                    changed += 1;
+                }
+                total += dithered.r as i64 - plain as i64;
+            }
+        }
+
+        // the noise actually does something, but doesn't drift the image brighter or darker
+        assert!(changed > 1000);
+        assert!(total.abs() < 4096 / 20, "{}", total);
+    }
+
+    #[test]
+    fn noise_is_triangular() {
+        let mut buckets = [0; 4];
+        for i in 0..40_000 {
+            let noise = triangular_noise(i % 200, i / 200);
+            assert!((-1.0..=1.0).contains(&noise));
+            buckets[((noise + 1.0) * 2.0) as usize] += 1;
+        }
+
+        // the outer quarters each hold 1/8th of a triangle, the middle ones 3/8ths
+        assert!((buckets[0] as f32 / 40_000.0 - 0.125).abs() < 0.01);
+        assert!((buckets[1] as f32 / 40_000.0 - 0.375).abs() < 0.01);
+        assert!((buckets[2] as f32 / 40_000.0 - 0.375).abs() < 0.01);
+        assert!((buckets[3] as f32 / 40_000.0 - 0.125).abs() < 0.01);
+    }
+}
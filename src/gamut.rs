@@ -0,0 +1,109 @@
+use crate::{EncodedColor, LinearColor};
+
+/// How to bring a linear color which is outside of sRGB (any channel below 0.0 or above 1.0)
+/// back inside it, for [from_linear_f32s_mapped](EncodedColor::from_linear_f32s_mapped).
+///
+/// None of these change colors which are already in gamut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GamutMapping {
+    /// Clamps each channel to 0.0 to 1.0 on its own, which is what
+    /// [to_encoded_space](LinearColor::to_encoded_space) does. This is the fastest, but bright
+    /// colors shift in hue (an orange that's too bright turns yellow) and lose detail, since
+    /// everything past the edge lands on the same value.
+    #[default]
+    Clamp,
+
+    /// Keeps the Oklch lightness and hue, and reduces chroma until the color fits, with
+    /// [gamut_clip_oklch](LinearColor::gamut_clip_oklch). Lightness past white or black is
+    /// clamped first. This looks the most like the original.
+    Desaturate,
+
+    /// Keeps the [luminance](LinearColor::luminance), and mixes in gray of that luminance until
+    /// the color fits. Luminance past white is clamped first. This keeps how much light there
+    /// is, which matters for things like exposure, at the cost of saturation.
+    PreserveLuminance,
+}
+
+impl EncodedColor {
+    /// Creates a color from linear `r, g, b, a` floats, with `mapping` deciding what happens to
+    /// colors outside of sRGB, like HDR values from lighting math. Alpha is clamped to 0.0 to
+    /// 1.0 and rounded to the nearest step.
+    pub fn from_linear_f32s_mapped(input: [f32; 4], mapping: GamutMapping) -> Self {
+        let [r, g, b, a] = input;
+        let linear = LinearColor::new(r, g, b, a);
+
+        let mapped = match mapping {
+            GamutMapping::Clamp => linear,
+            GamutMapping::Desaturate => linear.gamut_clip_oklch(),
+            GamutMapping::PreserveLuminance => {
+                let luminance = linear.luminance().clamp(0.0, 1.0);
+                linear.fit_toward_gray(luminance)
+            }
+        };
+
+        mapped.to_encoded_space().with_a(linear.alpha_u8())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPINGS: [GamutMapping; 3] = [
+        GamutMapping::Clamp,
+        GamutMapping::Desaturate,
+        GamutMapping::PreserveLuminance,
+    ];
+
+    #[test]
+    fn in_gamut() {
+        let sky = EncodedColor::new(107, 158, 190, 128);
+        for mapping in MAPPINGS {
+            assert_eq!(
+                EncodedColor::from_linear_f32s_mapped(sky.to_linear().to_array(), mapping),
+                sky
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_gamut() {
+        let hdr = [1.2, 0.5, 0.05, 1.0];
+
+        let clamped = EncodedColor::from_linear_f32s_mapped(hdr, GamutMapping::Clamp);
+        let desaturated = EncodedColor::from_linear_f32s_mapped(hdr, GamutMapping::Desaturate);
+        let preserved = EncodedColor::from_linear_f32s_mapped(hdr, GamutMapping::PreserveLuminance);
+
+        assert_ne!(clamped, desaturated);
+        assert_ne!(clamped, preserved);
+        assert_ne!(desaturated, preserved);
+
+        assert_eq!(clamped, LinearColor::new(1.0, 0.5, 0.05, 1.0).to_encoded_space());
+
+        // the luminance is kept, where clamping loses some
+        let luminance = LinearColor::new(1.2, 0.5, 0.05, 1.0).luminance();
+        assert!((preserved.luminance() - luminance).abs() < 0.01);
+        assert!(clamped.luminance() < luminance - 0.03);
+
+        // and the hue is kept, where clamping shifts it
+        let hue = LinearColor::new(1.2, 0.5, 0.05, 1.0).to_oklch().h;
+        assert!((desaturated.to_oklch().h - hue).abs() < 1.0);
+        assert!((clamped.to_oklch().h - hue).abs() > 1.0);
+
+        // negative channels are handled too
+        let negative = [-0.2, 0.5, 0.5, 1.0];
+        for mapping in MAPPINGS {
+            let color = EncodedColor::from_linear_f32s_mapped(negative, mapping);
+            assert_eq!(color.a, 255, "{:?}", mapping);
+        }
+        let preserved = EncodedColor::from_linear_f32s_mapped(negative, GamutMapping::PreserveLuminance);
+        let luminance = LinearColor::new(-0.2, 0.5, 0.5, 1.0).luminance();
+        assert!((preserved.luminance() - luminance).abs() < 0.01);
+
+        // and alpha is clamped
+        assert_eq!(
+            EncodedColor::from_linear_f32s_mapped([0.0, 0.0, 0.0, 3.0], GamutMapping::Clamp),
+            EncodedColor::BLACK
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{EncodedColor, HuePath, LinearColor, Oklch};
+use alloc::vec::Vec;
+
+/// How a [Gradient] moves through one segment, from one stop to the next.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Easing {
+    /// A constant speed, like [lerp](LinearColor::lerp).
+    #[default]
+    Linear,
+
+    /// Starts and ends gently, with the smoothstep curve `3t² - 2t³`, like
+    /// [smoothstep](LinearColor::smoothstep).
+    Smoothstep,
+
+    /// Any curve you like. It's given how far through the segment we are, from 0.0 to 1.0, and
+    /// should return how far through the color change to be. Non-capturing closures work here,
+    /// like `Easing::Custom(|t| t * t)`.
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+            Easing::Custom(f) => f(t),
+        }
+    }
+}
+
+/// Which color space a [Gradient] interpolates in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GradientSpace {
+    /// Linear sRGB, which blends the way light does. The middle of a gradient between two
+    /// saturated colors can look a bit washed out.
+    #[default]
+    Linear,
+
+    /// Oklch, going the short way around the hue wheel. This keeps colors saturated and the
+    /// lightness even, at the cost of passing through other hues (red to blue goes through
+    /// magenta). Anything outside of sRGB loses chroma to fit.
+    Oklch,
+}
+
+/// Builds a [Gradient] out of color stops, each with its own easing.
+///
+/// ```
+/// # use smol_rgb::{EncodedColor, Easing, GradientBuilder};
+/// let gradient = GradientBuilder::new()
+///     .stop(0.0, EncodedColor::BLACK)
+///     .eased_stop(0.5, EncodedColor::RED, Easing::Smoothstep)
+///     .stop(1.0, EncodedColor::WHITE)
+///     .build();
+///
+/// assert_eq!(gradient.sample(0.5), EncodedColor::RED);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GradientBuilder {
+    stops: Vec<Stop>,
+    space: GradientSpace,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Stop {
+    position: f32,
+    color: LinearColor,
+
+    // the easing of the segment from the previous stop to this one
+    easing: Easing,
+}
+
+impl GradientBuilder {
+    /// Creates a builder with no stops, which interpolates in linear space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stop of `color` at `position`, reached from the previous stop at a constant speed.
+    /// Positions can be in any range, and stops can be added in any order.
+    pub fn stop(self, position: f32, color: EncodedColor) -> Self {
+        self.eased_stop(position, color, Easing::Linear)
+    }
+
+    /// Adds a stop of `color` at `position`, like [stop](Self::stop), but the segment from the
+    /// previous stop to this one follows `easing`.
+    pub fn eased_stop(mut self, position: f32, color: EncodedColor, easing: Easing) -> Self {
+        self.stops.push(Stop {
+            position,
+            color: color.to_linear(),
+            easing,
+        });
+        self
+    }
+
+    /// Sets the color space to interpolate in. This is [GradientSpace::Linear] by default.
+    pub fn space(mut self, space: GradientSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Builds the [Gradient], sorting the stops by position. Stops at the same position keep
+    /// the order they were added in, so they make a hard edge.
+    pub fn build(mut self) -> Gradient {
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+
+        Gradient {
+            stops: self.stops,
+            space: self.space,
+        }
+    }
+}
+
+/// A multi-stop gradient, made with a [GradientBuilder], which can be sampled anywhere.
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    stops: Vec<Stop>,
+    space: GradientSpace,
+}
+
+impl Gradient {
+    /// Samples the gradient at `t`. Before the first stop, this is the first stop's color, and
+    /// past the last, the last stop's. With no stops at all, this is
+    /// [CLEAR](EncodedColor::CLEAR).
+    ///
+    /// Alpha is always interpolated straight, in whichever space.
+    pub fn sample(&self, t: f32) -> EncodedColor {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return EncodedColor::CLEAR;
+        };
+        if t <= first.position {
+            return encode(first.color);
+        }
+
+        // the first stop after `t`. Since `t` is past the first stop, there's always one before.
+        let Some(i) = self.stops.iter().position(|stop| stop.position > t) else {
+            return encode(last.color);
+        };
+        let (from, to) = (self.stops[i - 1], self.stops[i]);
+        let t = to.easing.apply((t - from.position) / (to.position - from.position));
+
+        let color = match self.space {
+            GradientSpace::Linear => from.color.lerp(to.color, t),
+            GradientSpace::Oklch => {
+                let (a, b) = (from.color.to_oklch(), to.color.to_oklch());
+                let lch = Oklch::new(a.l + (b.l - a.l) * t, a.c + (b.c - a.c) * t, lerp_hue(a, b, t));
+
+                LinearColor::from_oklch(lch, from.color.a + (to.color.a - from.color.a) * t).gamut_clip_oklch()
+            }
+        };
+
+        encode(color)
+    }
+}
+
+fn encode(color: LinearColor) -> EncodedColor {
+    color.to_encoded_space().with_a(color.alpha_u8())
+}
+
+/// Interpolates the hues of `a` and `b` the short way around. Grays have no hue, so they borrow
+/// the other color's, rather than sweeping through red.
+fn lerp_hue(a: Oklch, b: Oklch, t: f32) -> f32 {
+    // below this chroma, the hue is just noise
+    const GRAY_CHROMA: f32 = 0.0001;
+
+    let (from, to) = match (a.c < GRAY_CHROMA, b.c < GRAY_CHROMA) {
+        (true, false) => (b.h, b.h),
+        (false, true) => (a.h, a.h),
+        _ => HuePath::Shortest.fix_up(a.h, b.h),
+    };
+
+    (from + (to - from) * t) % 360.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient() {
+        let gradient = GradientBuilder::new()
+            .stop(1.0, EncodedColor::WHITE)
+            .stop(0.0, EncodedColor::BLACK)
+            .stop(0.5, EncodedColor::RED)
+            .build();
+
+        assert_eq!(gradient.sample(0.0), EncodedColor::BLACK);
+        assert_eq!(gradient.sample(0.5), EncodedColor::RED);
+        assert_eq!(gradient.sample(1.0), EncodedColor::WHITE);
+        assert_eq!(gradient.sample(-3.0), EncodedColor::BLACK);
+        assert_eq!(gradient.sample(3.0), EncodedColor::WHITE);
+
+        // the same as a plain lerp in linear space
+        let expected = EncodedColor::BLACK
+            .to_linear()
+            .lerp(EncodedColor::RED.to_linear(), 0.5)
+            .to_encoded_space();
+        assert_eq!(gradient.sample(0.25), expected);
+
+        assert_eq!(GradientBuilder::new().build().sample(0.5), EncodedColor::CLEAR);
+        let single = GradientBuilder::new().stop(0.3, EncodedColor::TEAL).build();
+        assert_eq!(single.sample(0.0), EncodedColor::TEAL);
+        assert_eq!(single.sample(1.0), EncodedColor::TEAL);
+    }
+
+    #[test]
+    fn easing() {
+        let (from, to) = (EncodedColor::new(20, 40, 200, 255), EncodedColor::new(250, 200, 20, 0));
+        let linear = GradientBuilder::new().stop(0.0, from).stop(1.0, to).build();
+        let eased = GradientBuilder::new()
+            .stop(0.0, from)
+            .eased_stop(1.0, to, Easing::Custom(|t| t * t))
+            .build();
+
+        assert_ne!(eased.sample(0.5), linear.sample(0.5));
+        assert_eq!(eased.sample(0.5), linear.sample(0.25));
+        assert_eq!(eased.sample(0.0), linear.sample(0.0));
+        assert_eq!(eased.sample(1.0), linear.sample(1.0));
+
+        let smooth = GradientBuilder::new()
+            .stop(0.0, from)
+            .eased_stop(1.0, to, Easing::Smoothstep)
+            .build();
+        assert_eq!(smooth.sample(0.5), linear.sample(0.5));
+        assert_ne!(smooth.sample(0.2), linear.sample(0.2));
+        assert_eq!(smooth.sample(0.2), linear.sample(0.104));
+
+        // easing only applies to its own segment
+        let both = GradientBuilder::new()
+            .stop(0.0, from)
+            .eased_stop(1.0, to, Easing::Custom(|t| t * t))
+            .stop(2.0, from)
+            .build();
+        assert_eq!(both.sample(0.5), eased.sample(0.5));
+        assert_eq!(both.sample(1.5), linear.sample(0.5));
+    }
+
+    #[test]
+    fn oklch() {
+        let (yellow, blue) = (
+            EncodedColor::new(230, 200, 30, 255),
+            EncodedColor::new(30, 30, 200, 255),
+        );
+        let linear = GradientBuilder::new().stop(0.0, yellow).stop(1.0, blue).build();
+        let oklch = GradientBuilder::new()
+            .stop(0.0, yellow)
+            .stop(1.0, blue)
+            .space(GradientSpace::Oklch)
+            .build();
+
+        assert!(yellow.delta_e(oklch.sample(0.0)) < 0.01);
+        assert!(blue.delta_e(oklch.sample(1.0)) < 0.01);
+
+        // Oklch keeps the middle saturated, where linear goes gray
+        assert!(oklch.sample(0.5).to_oklch().c > linear.sample(0.5).to_oklch().c + 0.03);
+
+        // grays don't sweep through red
+        let from_gray = GradientBuilder::new()
+            .stop(0.0, EncodedColor::new(128, 128, 128, 255))
+            .stop(1.0, blue)
+            .space(GradientSpace::Oklch)
+            .build();
+        assert!((from_gray.sample(0.5).to_oklch().h - blue.to_oklch().h).abs() < 5.0);
+    }
+}
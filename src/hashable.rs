@@ -0,0 +1,86 @@
+use crate::LinearColor;
+
+/// The exact bit patterns of a [LinearColor]'s channels, as `[r, g, b, a]`.
+///
+/// [LinearColor] can't be a `HashMap` or `BTreeMap` key, since `f32` isn't `Eq`, `Ord`, or
+/// `Hash`. This type is all three, so you can key on linear colors with
+/// [to_hashable](LinearColor::to_hashable).
+///
+/// Because this compares bits, not values, there are a couple of caveats:
+/// - `0.0` and `-0.0` are equal as floats, but have different bits, so they're different here.
+/// - NaN is never equal to itself as a float, but a NaN with the same bits *is* equal to itself
+///   here. NaNs with different payloads are still different.
+///
+/// If your colors might contain either, normalize them first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct LinearColorBits(pub [u32; 4]);
+
+impl LinearColor {
+    /// Gets the exact bit patterns of this color, which can be hashed and compared for equality.
+    /// See [LinearColorBits] for the caveats.
+    #[inline]
+    pub fn to_hashable(self) -> LinearColorBits {
+        LinearColorBits([self.r.to_bits(), self.g.to_bits(), self.b.to_bits(), self.a.to_bits()])
+    }
+
+    /// Recreates a color from the bit patterns made by [to_hashable](Self::to_hashable).
+    #[inline]
+    pub fn from_hashable(bits: LinearColorBits) -> Self {
+        let [r, g, b, a] = bits.0;
+        Self::new(
+            f32::from_bits(r),
+            f32::from_bits(g),
+            f32::from_bits(b),
+            f32::from_bits(a),
+        )
+    }
+}
+
+impl From<LinearColor> for LinearColorBits {
+    fn from(o: LinearColor) -> Self {
+        o.to_hashable()
+    }
+}
+
+impl From<LinearColorBits> for LinearColor {
+    fn from(o: LinearColorBits) -> Self {
+        LinearColor::from_hashable(o)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let color = LinearColor::new(0.2, 0.4, 0.9, 0.5);
+        assert_eq!(LinearColor::from_hashable(color.to_hashable()), color);
+        assert_eq!(LinearColor::from(LinearColorBits::from(color)), color);
+
+        // the caveats
+        assert_ne!(
+            LinearColor::new(0.0, 0.0, 0.0, 1.0).to_hashable(),
+            LinearColor::new(-0.0, 0.0, 0.0, 1.0).to_hashable()
+        );
+        let nan = LinearColor::new(f32::NAN, 0.0, 0.0, 1.0);
+        assert_eq!(nan.to_hashable(), nan.to_hashable());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_map_key() {
+        let mut names = std::collections::HashMap::new();
+        names.insert(LinearColor::new(0.2, 0.4, 0.9, 1.0).to_hashable(), "sky");
+        names.insert(LinearColor::new(1.0, 0.0, 0.0, 1.0).to_hashable(), "red");
+
+        assert_eq!(
+            names.get(&LinearColor::new(0.2, 0.4, 0.9, 1.0).to_hashable()),
+            Some(&"sky")
+        );
+        assert_eq!(names.get(&LinearColor::new(0.2, 0.4, 0.9, 0.5).to_hashable()), None);
+
+        let (key, _) = names.iter().find(|(_, v)| **v == "red").unwrap();
+        assert_eq!(LinearColor::from_hashable(*key), LinearColor::new(1.0, 0.0, 0.0, 1.0));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::EncodedColor;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+impl EncodedColor {
+    /// Writes this color as a lowercase `#rrggbbaa` hex string into `buf`, and returns the
+    /// written string. This needs no allocator, so it works on `no_std` targets where
+    /// `format!` isn't available:
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// let mut buf = [0; 9];
+    /// assert_eq!(EncodedColor::new(107, 158, 190, 255).write_hex(&mut buf), "#6b9ebeff");
+    /// ```
+    ///
+    /// Unlike the [LowerHex](core::fmt::LowerHex) impl, this always writes all 8 digits.
+    pub fn write_hex(self, buf: &mut [u8; 9]) -> &str {
+        buf[0] = b'#';
+        buf[1..].copy_from_slice(&self.to_hex_bytes());
+
+        core::str::from_utf8(buf).expect("hex digits are always ascii")
+    }
+
+    /// The lowercase ASCII hex digits of this color, `rrggbbaa`, without the `#`. This is a
+    /// `const fn`, so you can bake hex strings into statics on targets without an allocator:
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// const SKY: [u8; 8] = EncodedColor::new(107, 158, 190, 255).to_hex_bytes();
+    /// assert_eq!(&SKY, b"6b9ebeff");
+    /// ```
+    pub const fn to_hex_bytes(self) -> [u8; 8] {
+        let channels = [self.r, self.g, self.b, self.a];
+        let mut bytes = [0; 8];
+
+        let mut i = 0;
+        while i < 4 {
+            bytes[i * 2] = HEX_DIGITS[(channels[i] >> 4) as usize];
+            bytes[i * 2 + 1] = HEX_DIGITS[(channels[i] & 0xf) as usize];
+            i += 1;
+        }
+
+        bytes
+    }
+
+    /// Formats this color as a lowercase `#rrggbbaa` line, always 9 characters long. This
+    /// format is stable, so it's meant for golden files and snapshot tests (like with `insta`),
+    /// where you don't want the output of a `Debug` impl.
+    #[cfg(feature = "alloc")]
+    pub fn to_debug_hex_line(self) -> String {
+        let mut buf = [0; 9];
+
+        String::from(self.write_hex(&mut buf))
+    }
+
+    /// Formats a whole palette with [to_debug_hex_line](Self::to_debug_hex_line), one color per
+    /// line. Every line, including the last, ends with a `\n`.
+    #[cfg(feature = "alloc")]
+    pub fn to_debug_hex_lines(colors: &[EncodedColor]) -> String {
+        let mut output = String::with_capacity(colors.len() * 10);
+        let mut buf = [0; 9];
+
+        for color in colors {
+            output.push_str(color.write_hex(&mut buf));
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_hex() {
+        let mut buf = [0u8; 9];
+
+        assert_eq!(EncodedColor::new(107, 158, 190, 255).write_hex(&mut buf), "#6b9ebeff");
+        assert_eq!(EncodedColor::CLEAR.write_hex(&mut buf), "#00000000");
+        assert_eq!(EncodedColor::new(1, 2, 171, 16).write_hex(&mut buf), "#0102ab10");
+        assert_eq!(&buf, b"#0102ab10");
+
+        // and it parses right back
+        let color = EncodedColor::new(250, 5, 99, 128);
+        assert_eq!(color.write_hex(&mut buf).parse(), Ok(color));
+    }
+
+    #[test]
+    fn to_hex_bytes() {
+        const SKY: [u8; 8] = EncodedColor::new(107, 158, 190, 255).to_hex_bytes();
+        assert_eq!(&SKY, b"6b9ebeff");
+        assert_eq!(&EncodedColor::new(1, 2, 171, 16).to_hex_bytes(), b"0102ab10");
+
+        // it's write_hex without the `#`
+        let mut buf = [0u8; 9];
+        for color in [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::CLEAR,
+            EncodedColor::new(250, 5, 99, 128),
+        ] {
+            let expected = color.write_hex(&mut buf);
+            assert_eq!(&color.to_hex_bytes(), &expected.as_bytes()[1..]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn debug_hex_lines() {
+        assert_eq!(EncodedColor::new(107, 158, 190, 255).to_debug_hex_line(), "#6b9ebeff");
+        assert_eq!(EncodedColor::new(0, 10, 0, 1).to_debug_hex_line(), "#000a0001");
+
+        let palette = [EncodedColor::RED, EncodedColor::TEAL, EncodedColor::CLEAR];
+        assert_eq!(
+            EncodedColor::to_debug_hex_lines(&palette),
+            "#ff0000ff\n#00ffffff\n#00000000\n"
+        );
+        assert_eq!(EncodedColor::to_debug_hex_lines(&[]), "");
+    }
+}
@@ -0,0 +1,439 @@
+use crate::{EncodedColor, hsluv::wrap_hue};
+
+/// Which way around the hue wheel to go when interpolating hues, like in
+/// [lerp_hsl](EncodedColor::lerp_hsl). These are the same as CSS's hue interpolation methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HuePath {
+    /// Whichever way is shorter. This is usually what you want.
+    #[default]
+    Shortest,
+
+    /// Whichever way is longer, for the "all the way around the rainbow" look.
+    Longest,
+
+    /// Always go up in hue (red to yellow to green...), wrapping past 360 if needed.
+    Increasing,
+
+    /// Always go down in hue (red to magenta to blue...), wrapping past 0 if needed.
+    Decreasing,
+}
+
+impl HuePath {
+    /// Adjusts the two hues (in degrees, from 0 to 360) so that going straight from one to the
+    /// other goes the right way around.
+    pub(crate) fn fix_up(self, from: f32, to: f32) -> (f32, f32) {
+        let difference = to - from;
+
+        match self {
+            HuePath::Shortest if difference > 180.0 => (from + 360.0, to),
+            HuePath::Shortest if difference < -180.0 => (from, to + 360.0),
+            HuePath::Longest if 0.0 < difference && difference < 180.0 => (from + 360.0, to),
+            HuePath::Longest if -180.0 < difference && difference <= 0.0 => (from, to + 360.0),
+            HuePath::Increasing if difference < 0.0 => (from, to + 360.0),
+            HuePath::Decreasing if difference > 0.0 => (from + 360.0, to),
+            _ => (from, to),
+        }
+    }
+}
+
+// all the math below happens in fixed point, where this is 1.0. It's a multiple of 100 (for the
+// percents) and 60 (for the hue sectors), so all of our divisions are exact until the very end.
+const ONE: i64 = 100 * 100 * 60;
+
+impl EncodedColor {
+    /// Creates a color from hue, saturation, and lightness, like CSS's `hsl()`. The hue is in
+    /// degrees (and wraps at 360), and saturation and lightness are percents (clamped to 100).
+    /// Alpha is 255.
+    ///
+    /// This takes whole numbers, rather than floats, so that it can be a `const fn`: the math is
+    /// all done in fixed point, so you can declare themes as consts. Like CSS, this works on the
+    /// encoded values, so it isn't perceptually uniform. For that, see
+    /// [from_oklch](Self::from_oklch).
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// const ACCENT: EncodedColor = EncodedColor::from_hsl(210, 50, 60);
+    /// assert_eq!(ACCENT, EncodedColor::new(102, 153, 204, 255));
+    /// ```
+    pub const fn from_hsl(h: u16, s: u8, l: u8) -> Self {
+        let s = clamp_percent(s);
+        let l = clamp_percent(l) * (ONE / 100);
+
+        let chroma = (ONE - (2 * l - ONE).abs()) * s / 100;
+        Self::from_hue_chroma(h, chroma, l - chroma / 2)
+    }
+
+    /// Creates a color from hue, saturation, and value (sometimes called HSB), like the pickers
+    /// in most image editors. The hue is in degrees (and wraps at 360), and saturation and value
+    /// are percents (clamped to 100). Alpha is 255.
+    ///
+    /// Like [from_hsl](Self::from_hsl), this takes whole numbers so that it can be a `const fn`.
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// const LIME: EncodedColor = EncodedColor::from_hsv(120, 100, 100);
+    /// assert_eq!(LIME, EncodedColor::GREEN);
+    /// ```
+    pub const fn from_hsv(h: u16, s: u8, v: u8) -> Self {
+        let s = clamp_percent(s);
+        let v = clamp_percent(v) * (ONE / 100);
+
+        let chroma = v * s / 100;
+        Self::from_hue_chroma(h, chroma, v - chroma)
+    }
+
+    /// The shared end of HSL and HSV: places the chroma according to the hue, then adds `m` to
+    /// every channel.
+    const fn from_hue_chroma(h: u16, chroma: i64, m: i64) -> Self {
+        let h = (h % 360) as i64;
+        let x = chroma * (60 - (h % 120 - 60).abs()) / 60;
+
+        let (r, g, b) = match h / 60 {
+            0 => (chroma, x, 0),
+            1 => (x, chroma, 0),
+            2 => (0, chroma, x),
+            3 => (0, x, chroma),
+            4 => (x, 0, chroma),
+            _ => (chroma, 0, x),
+        };
+
+        Self::new(to_u8(r + m), to_u8(g + m), to_u8(b + m), 255)
+    }
+}
+
+impl EncodedColor {
+    /// Interpolates between this color and `other` in HSL, going around the hue wheel along
+    /// `hue_path`. Saturation, lightness, and alpha are interpolated straight.
+    ///
+    /// This is mostly for UI effects where you want to control the hue, like a rainbow sweep.
+    /// HSL isn't perceptually uniform, so for smooth blends,
+    /// [LinearColor::lerp](crate::LinearColor::lerp) or Oklch are better choices. Grays (and
+    /// black and white) have no hue, so they borrow the other color's, rather than sweeping
+    /// through red. `t` is clamped from 0.0 to 1.0.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn lerp_hsl(self, other: EncodedColor, t: f32, hue_path: HuePath) -> EncodedColor {
+        let (h1, s1, l1) = self.to_hsl_f32();
+        let (h2, s2, l2) = other.to_hsl_f32();
+
+        let (h1, h2) = match (s1 == 0.0, s2 == 0.0) {
+            (true, false) => (h2, h2),
+            (false, true) => (h1, h1),
+            _ => hue_path.fix_up(h1, h2),
+        };
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        let mut color = Self::from_hsl_f32(wrap_hue(lerp(h1, h2)), lerp(s1, s2), lerp(l1, l2));
+        color.a = (lerp(self.a as f32, other.a as f32).clamp(0.0, 255.0) + 0.5) as u8;
+        color
+    }
+
+    /// Hue in degrees, and saturation and lightness from 0.0 to 1.0. Grays have a hue and
+    /// saturation of 0.
+    pub(crate) fn to_hsl_f32(self) -> (f32, f32, f32) {
+        let [r, g, b, _] = self.to_encoded_f32s();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let l = (max + min) / 2.0;
+
+        if chroma == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let h = if max == r {
+            (g - b) / chroma
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        } * 60.0;
+
+        let h = if h < 0.0 { h + 360.0 } else { h };
+        (h, chroma / (1.0 - (2.0 * l - 1.0).abs()), l)
+    }
+
+    pub(crate) fn from_hsl_f32(h: f32, s: f32, l: f32) -> Self {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+        let m = l - chroma / 2.0;
+
+        let (r, g, b) = match (h / 60.0) as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let to_u8 = |c: f32| ((c + m).clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+
+        Self::new(to_u8(r), to_u8(g), to_u8(b), 255)
+    }
+}
+
+/// Cycles a color's hue around the HSL wheel over time, for long-running rainbow effects. Call
+/// [tick](Self::tick) every frame.
+///
+/// Converting a color to HSL, shifting it, and converting it back every frame rounds to a u8
+/// each time, so after a while the color drifts and loses saturation. This converts the starting
+/// color once, and keeps how far it has gone around as an `f64`, so a cycle lands back on the
+/// starting color however many frames it took. Saturation, lightness, and alpha never change.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HueCycler {
+    hue: f32,
+    saturation: f32,
+    lightness: f32,
+    alpha: u8,
+    degrees_per_second: f32,
+    offset: f64,
+}
+
+impl HueCycler {
+    /// Creates a cycler starting at `color`, which moves `degrees_per_second` around the hue
+    /// wheel. Negative speeds go backwards, from red to magenta to blue. Grays have no hue, so
+    /// they stay gray.
+    pub fn new(color: EncodedColor, degrees_per_second: f32) -> Self {
+        let (hue, saturation, lightness) = color.to_hsl_f32();
+
+        Self {
+            hue,
+            saturation,
+            lightness,
+            alpha: color.a,
+            degrees_per_second,
+            offset: 0.0,
+        }
+    }
+
+    /// Advances the hue by `dt` seconds' worth, and returns the new color.
+    pub fn tick(&mut self, dt: f32) -> EncodedColor {
+        self.offset = (self.offset + self.degrees_per_second as f64 * dt as f64) % 360.0;
+        if self.offset < 0.0 {
+            self.offset += 360.0;
+        }
+
+        self.color()
+    }
+
+    /// The current color.
+    pub fn color(&self) -> EncodedColor {
+        let hue = (self.hue as f64 + self.offset) % 360.0;
+
+        let mut color = EncodedColor::from_hsl_f32(hue as f32, self.saturation, self.lightness);
+        color.a = self.alpha;
+        color
+    }
+}
+
+/// A distribution of opaque colors with hues in a band of the HSL wheel, and a fixed saturation
+/// and lightness, for constrained randomness like "random greens" in procedural generation.
+///
+/// ```
+/// use rand::Rng;
+/// use smol_rgb::HueRange;
+///
+/// let greens = HueRange::new(90.0, 150.0, 0.6, 0.4);
+/// let grass = rand::thread_rng().sample(greens);
+/// assert!(grass.g > grass.r && grass.g > grass.b);
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct HueRange {
+    /// Where the band starts, in degrees.
+    pub min_deg: f32,
+
+    /// Where the band ends, in degrees. If this is less than `min_deg`, the band wraps around
+    /// through 360, so `330.0` to `30.0` is the reds. If it's 360 or more past `min_deg`, the
+    /// band is the whole wheel.
+    pub max_deg: f32,
+
+    /// The saturation of every color, from 0.0 to 1.0.
+    pub saturation: f32,
+
+    /// The lightness of every color, from 0.0 to 1.0.
+    pub lightness: f32,
+}
+
+#[cfg(feature = "rand")]
+impl HueRange {
+    /// Creates a new hue range.
+    pub const fn new(min_deg: f32, max_deg: f32, saturation: f32, lightness: f32) -> Self {
+        Self {
+            min_deg,
+            max_deg,
+            saturation,
+            lightness,
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand::distributions::Distribution<EncodedColor> for HueRange {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> EncodedColor {
+        // the span comes from the raw values, so a band of 360 or more is the whole wheel, rather
+        // than wrapping down to nothing
+        let span = self.max_deg - self.min_deg;
+        let span = if span >= 360.0 { 360.0 } else { wrap_hue(span) };
+
+        let hue = wrap_hue(self.min_deg + rng.gen::<f32>() * span);
+        EncodedColor::from_hsl_f32(hue, self.saturation.clamp(0.0, 1.0), self.lightness.clamp(0.0, 1.0))
+    }
+}
+
+const fn clamp_percent(p: u8) -> i64 {
+    if p > 100 { 100 } else { p as i64 }
+}
+
+const fn to_u8(c: i64) -> u8 {
+    ((c * 255 + ONE / 2) / ONE) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hsl() {
+        const ORANGE: EncodedColor = EncodedColor::from_hsl(30, 100, 50);
+        const GRAY: EncodedColor = EncodedColor::from_hsl(0, 0, 50);
+        assert_eq!(ORANGE, EncodedColor::new(255, 128, 0, 255));
+        assert_eq!(GRAY, EncodedColor::new(128, 128, 128, 255));
+
+        assert_eq!(EncodedColor::from_hsl(0, 100, 50), EncodedColor::RED);
+        assert_eq!(EncodedColor::from_hsl(360, 100, 50), EncodedColor::RED);
+        assert_eq!(EncodedColor::from_hsl(240, 100, 50), EncodedColor::BLUE);
+        assert_eq!(
+            EncodedColor::from_hsl(300, 76, 72),
+            EncodedColor::new(238, 129, 238, 255)
+        );
+        assert_eq!(EncodedColor::from_hsl(123, 255, 100), EncodedColor::WHITE);
+        assert_eq!(EncodedColor::from_hsl(123, 100, 0), EncodedColor::BLACK);
+    }
+
+    #[test]
+    fn from_hsv() {
+        const TEAL: EncodedColor = EncodedColor::from_hsv(180, 100, 50);
+        assert_eq!(TEAL, EncodedColor::new(0, 128, 128, 255));
+
+        assert_eq!(EncodedColor::from_hsv(60, 100, 100), EncodedColor::YELLOW);
+        assert_eq!(
+            EncodedColor::from_hsv(330, 50, 80),
+            EncodedColor::new(204, 102, 153, 255)
+        );
+        assert_eq!(EncodedColor::from_hsv(45, 0, 100), EncodedColor::WHITE);
+        assert_eq!(EncodedColor::from_hsv(45, 100, 0), EncodedColor::BLACK);
+    }
+
+    #[test]
+    fn lerp_hsl() {
+        let (red, blue) = (EncodedColor::RED, EncodedColor::BLUE);
+
+        // red is at 0 and blue is at 240, so the short way is through magenta...
+        assert_eq!(red.lerp_hsl(blue, 0.5, HuePath::Shortest), EncodedColor::FUCHSIA);
+        assert_eq!(red.lerp_hsl(blue, 0.5, HuePath::Decreasing), EncodedColor::FUCHSIA);
+
+        // ...and the long way is through green.
+        assert_eq!(red.lerp_hsl(blue, 0.5, HuePath::Longest), EncodedColor::GREEN);
+        assert_eq!(red.lerp_hsl(blue, 0.5, HuePath::Increasing), EncodedColor::GREEN);
+        assert_eq!(blue.lerp_hsl(red, 0.5, HuePath::Increasing), EncodedColor::FUCHSIA);
+
+        for path in [
+            HuePath::Shortest,
+            HuePath::Longest,
+            HuePath::Increasing,
+            HuePath::Decreasing,
+        ] {
+            assert_eq!(red.lerp_hsl(blue, 0.0, path), red);
+            assert_eq!(red.lerp_hsl(blue, 1.0, path), blue);
+            assert_eq!(red.lerp_hsl(blue, -0.5, path), red);
+            assert_eq!(red.lerp_hsl(blue, 1.5, path), blue);
+        }
+
+        // grays borrow the other hue, so white to red is a pink, not a sweep through the hues
+        let pink = EncodedColor::WHITE.lerp_hsl(red, 0.5, HuePath::Shortest);
+        assert_eq!(pink, EncodedColor::new(223, 159, 159, 255));
+
+        assert_eq!(red.with_a(0).lerp_hsl(red, 0.5, HuePath::Shortest).a, 128);
+    }
+
+    #[test]
+    fn hue_cycler() {
+        let sky = EncodedColor::new(107, 158, 190, 200);
+        let mut cycler = HueCycler::new(sky, 90.0);
+        assert_eq!(cycler.color(), sky);
+
+        // a quarter turn a frame
+        let red = EncodedColor::RED;
+        let mut red_cycler = HueCycler::new(red, 90.0);
+        assert_eq!(red_cycler.tick(1.0), EncodedColor::from_hsl(90, 100, 50));
+        assert_eq!(red_cycler.tick(1.0), EncodedColor::from_hsl(180, 100, 50));
+        assert_eq!(red_cycler.tick(2.0), red);
+
+        // many small steps don't drift, even over several turns
+        for _ in 0..3 {
+            for _ in 0..240 {
+                cycler.tick(1.0 / 60.0);
+            }
+            assert!(sky.delta_e(cycler.color()) < 0.01, "{:?}", cycler.color());
+        }
+        assert_eq!(cycler.color().a, 200);
+
+        // and backwards works too
+        let mut backwards = HueCycler::new(red, -120.0);
+        assert_eq!(backwards.tick(1.0), EncodedColor::BLUE);
+        assert_eq!(backwards.tick(2.0), red);
+
+        let mut gray = HueCycler::new(EncodedColor::new(90, 90, 90, 255), 90.0);
+        assert_eq!(gray.tick(1.5), EncodedColor::new(90, 90, 90, 255));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn hue_range() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+
+        let greens = HueRange::new(90.0, 150.0, 0.6, 0.4);
+        for _ in 0..1000 {
+            let color: EncodedColor = rng.sample(greens);
+            let (h, s, l) = color.to_hsl_f32();
+
+            // rounding to u8s can nudge the hue a little
+            assert!((89.0..=151.0).contains(&h), "{:?} has hue {}", color, h);
+            assert!((s - 0.6).abs() < 0.01 && (l - 0.4).abs() < 0.01, "{:?}", color);
+            assert_eq!(color.a, 255);
+        }
+
+        // bands can wrap around through 360
+        let reds = HueRange::new(330.0, 30.0, 1.0, 0.5);
+        let (mut below, mut above) = (0, 0);
+        for _ in 0..1000 {
+            let (h, _, _) = rng.sample::<EncodedColor, _>(reds).to_hsl_f32();
+            match h {
+                h if h >= 329.0 => below += 1,
+                h if h <= 31.0 => above += 1,
+                h => panic!("hue {} is outside the band", h),
+            }
+        }
+        assert!(below > 400 && above > 400, "{} {}", below, above);
+
+        assert_eq!(
+            rng.sample::<EncodedColor, _>(HueRange::new(240.0, 240.0, 1.0, 0.5)),
+            EncodedColor::BLUE
+        );
+
+        // and a full band is the whole wheel, not a single hue
+        for (min, max) in [(0.0, 360.0), (90.0, 450.0), (-180.0, 720.0)] {
+            let rainbow = HueRange::new(min, max, 1.0, 0.5);
+            let (mut lowest, mut highest) = (360.0f32, 0.0f32);
+            for _ in 0..1000 {
+                let (h, _, _) = rng.sample::<EncodedColor, _>(rainbow).to_hsl_f32();
+                lowest = lowest.min(h);
+                highest = highest.max(h);
+            }
+            assert!(lowest < 10.0 && highest > 350.0, "{} {}", lowest, highest);
+        }
+    }
+}
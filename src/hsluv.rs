@@ -0,0 +1,205 @@
+use crate::{EncodedColor, LinearColor, math};
+
+// linear sRGB to CIE XYZ (D65), and back. These are the matrices the HSLuv reference uses.
+#[allow(clippy::excessive_precision)]
+pub(crate) const TO_XYZ: [[f32; 3]; 3] = [
+    [0.41239080, 0.35758434, 0.18048079],
+    [0.21263901, 0.71516868, 0.07219232],
+    [0.01933082, 0.11919478, 0.95053215],
+];
+#[allow(clippy::excessive_precision)]
+pub(crate) const FROM_XYZ: [[f32; 3]; 3] = [
+    [3.24096994, -1.53738318, -0.49861076],
+    [-0.96924364, 1.87596750, 0.04155506],
+    [0.05563008, -0.20397696, 1.05697151],
+];
+
+// the u' and v' of the D65 white point
+const REF_U: f32 = 0.19783;
+const REF_V: f32 = 0.46832;
+
+// CIE's constants for the linear part of the lightness curve near black
+pub(crate) const KAPPA: f32 = 903.2963;
+pub(crate) const EPSILON: f32 = 0.008856452;
+
+impl EncodedColor {
+    /// Creates a color from [HSLuv](https://www.hsluv.org/) hue, saturation, and lightness,
+    /// with the given alpha. The hue is in degrees, and saturation and lightness go from 0.0
+    /// to 100.0.
+    ///
+    /// HSLuv is a friendlier version of CIELUV, shaped like HSL, which is nice for color
+    /// pickers: unlike HSL, every color with the same lightness looks about as light as every
+    /// other. Saturation is a percent of the most colorful color sRGB can show at that hue and
+    /// lightness, so every input gives a valid color.
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// let pink = EncodedColor::from_hsluv(0.0, 100.0, 53.2, 255);
+    /// assert_eq!(pink.to_hsluv().0.round(), 0.0);
+    /// ```
+    pub fn from_hsluv(h: f32, s: f32, l: f32, a: u8) -> Self {
+        let l = l.clamp(0.0, 100.0);
+        let h = wrap_hue(h);
+
+        if l > 99.99999 {
+            return EncodedColor::WHITE.with_a(a);
+        }
+        if l < 0.00001 {
+            return EncodedColor::BLACK.with_a(a);
+        }
+
+        let chroma = max_chroma(l, h) * s.clamp(0.0, 100.0) / 100.0;
+        let h = h.to_radians();
+        let (x, y, z) = luv_to_xyz(l, chroma * math::cos(h), chroma * math::sin(h));
+
+        let [r, g, b] = FROM_XYZ.map(|row| (row[0] * x + row[1] * y + row[2] * z).clamp(0.0, 1.0));
+        LinearColor::new(r, g, b, 1.0).to_encoded_space().with_a(a)
+    }
+
+    /// Converts this color into [HSLuv](https://www.hsluv.org/) hue, saturation, and lightness,
+    /// dropping alpha. See [from_hsluv](Self::from_hsluv) for the ranges.
+    ///
+    /// Grays, black, and white don't have a hue, so they get a hue of 0.0.
+    pub fn to_hsluv(self) -> (f32, f32, f32) {
+        let linear = self.to_linear();
+        let [x, y, z] = TO_XYZ.map(|row| row[0] * linear.r + row[1] * linear.g + row[2] * linear.b);
+        let (l, u, v) = xyz_to_luv(x, y, z);
+
+        let chroma = math::sqrt(u * u + v * v);
+        let h = if chroma < 0.0001 {
+            0.0
+        } else {
+            wrap_hue(math::atan2(v, u).to_degrees())
+        };
+
+        if !(0.00001..=99.99999).contains(&l) {
+            return (h, 0.0, l.clamp(0.0, 100.0));
+        }
+
+        let s = (chroma / max_chroma(l, h) * 100.0).min(100.0);
+        (h, s, l)
+    }
+}
+
+/// The most chroma, in CIELUV, that sRGB can show at this lightness and hue (in degrees).
+/// The edges of sRGB are where a channel hits 0.0 or 1.0; at a fixed lightness, each of those
+/// six edges is a straight line in the uv plane, so we find the closest one along the hue.
+fn max_chroma(l: f32, h: f32) -> f32 {
+    let sub1 = (l + 16.0) * (l + 16.0) * (l + 16.0) / 1560896.0;
+    let sub2 = if sub1 > EPSILON { sub1 } else { l / KAPPA };
+    let h = h.to_radians();
+    let (sin, cos) = (math::sin(h), math::cos(h));
+
+    let mut max = f32::MAX;
+    for [m1, m2, m3] in FROM_XYZ {
+        for t in [0.0, 1.0] {
+            let top1 = (284517.0 * m1 - 94839.0 * m3) * sub2;
+            let top2 = (838422.0 * m3 + 769860.0 * m2 + 731718.0 * m1) * l * sub2 - 769860.0 * t * l;
+            let bottom = (632260.0 * m3 - 126452.0 * m2) * sub2 + 126452.0 * t;
+
+            let length = (top2 / bottom) / (sin - (top1 / bottom) * cos);
+            if length >= 0.0 {
+                max = max.min(length);
+            }
+        }
+    }
+
+    max
+}
+
+/// Wraps a hue in degrees into 0 to 360, including negative ones, which `%` alone doesn't.
+pub(crate) fn wrap_hue(h: f32) -> f32 {
+    let h = h % 360.0;
+    if h < 0.0 { h + 360.0 } else { h }
+}
+
+fn xyz_to_luv(x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    let l = if y <= EPSILON {
+        y * KAPPA
+    } else {
+        116.0 * math::cbrt(y) - 16.0
+    };
+    if l == 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let divider = x + 15.0 * y + 3.0 * z;
+    let u = 4.0 * x / divider;
+    let v = 9.0 * y / divider;
+
+    (l, 13.0 * l * (u - REF_U), 13.0 * l * (v - REF_V))
+}
+
+fn luv_to_xyz(l: f32, u: f32, v: f32) -> (f32, f32, f32) {
+    let u = u / (13.0 * l) + REF_U;
+    let v = v / (13.0 * l) + REF_V;
+
+    let y = if l <= 8.0 {
+        l / KAPPA
+    } else {
+        let cube = (l + 16.0) / 116.0;
+        cube * cube * cube
+    };
+    let x = -(9.0 * y * u) / ((u - 4.0) * v - u * v);
+    let z = (9.0 * y - 15.0 * v * y - v * x) / (3.0 * v);
+
+    (x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        // from the HSLuv reference implementation's snapshot
+        let (h, s, l) = EncodedColor::new(0xff, 0x00, 0x00, 255).to_hsluv();
+        assert!((h - 12.177).abs() < 0.01, "{}", h);
+        assert!((s - 100.0).abs() < 0.01, "{}", s);
+        assert!((l - 53.237).abs() < 0.01, "{}", l);
+
+        assert_eq!(
+            EncodedColor::from_hsluv(12.177, 100.0, 53.237, 9),
+            EncodedColor::RED.with_a(9)
+        );
+        assert_eq!(EncodedColor::from_hsluv(200.0, 50.0, 100.0, 255), EncodedColor::WHITE);
+        assert_eq!(EncodedColor::from_hsluv(200.0, 50.0, 0.0, 255), EncodedColor::BLACK);
+        assert_eq!(EncodedColor::WHITE.to_hsluv(), (0.0, 0.0, 100.0));
+    }
+
+    #[test]
+    fn roundtrip() {
+        for color in [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(200, 30, 30, 255),
+            EncodedColor::new(40, 170, 60, 255),
+            EncodedColor::new(250, 240, 10, 255),
+            EncodedColor::new(5, 3, 40, 255),
+        ] {
+            let (h, s, l) = color.to_hsluv();
+            let back = EncodedColor::from_hsluv(h, s, l, 255);
+            for (a, b) in [(color.r, back.r), (color.g, back.g), (color.b, back.b)] {
+                assert!(a.abs_diff(b) <= 1, "{:?} {:?}", color, back);
+            }
+        }
+    }
+
+    #[test]
+    fn same_lightness_looks_as_light() {
+        for l in [20.0, 50.0, 80.0] {
+            let expected = EncodedColor::from_hsluv(0.0, 0.0, l, 255).luminance();
+
+            for h in [0.0, 60.0, 130.0, 200.0, 265.0, 320.0] {
+                let luminance = EncodedColor::from_hsluv(h, 100.0, l, 255).luminance();
+                assert!(
+                    (luminance - expected).abs() < 0.01,
+                    "{} {} {} {}",
+                    l,
+                    h,
+                    luminance,
+                    expected
+                );
+            }
+        }
+    }
+}
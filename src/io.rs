@@ -0,0 +1,58 @@
+use crate::EncodedColor;
+use std::io::{self, Read, Write};
+
+impl EncodedColor {
+    /// Reads one color from `reader`, as 4 bytes in `r, g, b, a` order. This is handy for
+    /// streaming raw RGBA pixels out of a file or socket, without loading them all at once.
+    ///
+    /// If the reader runs out before all 4 bytes, this is an [UnexpectedEof] error.
+    ///
+    /// [UnexpectedEof]: io::ErrorKind::UnexpectedEof
+    pub fn read_from(reader: &mut impl Read) -> io::Result<EncodedColor> {
+        let mut bytes = [0; 4];
+        reader.read_exact(&mut bytes)?;
+
+        Ok(EncodedColor::new(bytes[0], bytes[1], bytes[2], bytes[3]))
+    }
+
+    /// Writes this color to `writer`, as 4 bytes in `r, g, b, a` order. This is the opposite of
+    /// [read_from](Self::read_from).
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&[self.r, self.g, self.b, self.a])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Cursor, vec::Vec};
+
+    #[test]
+    fn round_trip() {
+        let colors = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::CLEAR,
+            EncodedColor::new(1, 2, 3, 4),
+        ];
+
+        let mut cursor = Cursor::new(Vec::new());
+        for color in colors {
+            color.write_to(&mut cursor).unwrap();
+        }
+        assert_eq!(cursor.get_ref()[..4], [107, 158, 190, 255]);
+        assert_eq!(cursor.get_ref().len(), 12);
+
+        cursor.set_position(0);
+        for color in colors {
+            assert_eq!(EncodedColor::read_from(&mut cursor).unwrap(), color);
+        }
+
+        // and there's nothing left
+        let err = EncodedColor::read_from(&mut cursor).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // a short read is an error, too
+        let err = EncodedColor::read_from(&mut Cursor::new([1, 2, 3])).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
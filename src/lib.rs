@@ -8,8 +8,78 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::fmt;
 
+mod adjust;
+mod ansi;
+mod blend;
+mod color_blindness;
+mod convert;
+mod dither;
+mod gamut;
+#[cfg(feature = "alloc")]
+mod gradient;
+mod hashable;
+mod hex;
+mod hsl;
+mod hsluv;
+#[cfg(feature = "std")]
+mod io;
+mod lms;
+mod luminance;
+#[cfg(feature = "alloc")]
+mod lut3d;
+mod macros;
+mod math;
+mod mix;
+mod oklab;
+mod palette;
+mod parse;
+mod premultiply;
+mod quantize;
+mod rgb;
+mod sort;
+mod theme;
+mod tonemap;
+
+pub mod prelude;
+
+#[cfg(feature = "serde")]
+pub mod serde_encoded_hex;
+#[cfg(feature = "serde")]
+pub mod serde_packed_u32;
+#[cfg(feature = "serde")]
+pub mod serde_partial;
+#[cfg(feature = "serde")]
+pub mod serde_struct;
+
+pub use ansi::ANSI_RESET;
+pub use blend::{AlphaMode, BlendFactor, BlendMode, BlendOp};
+pub use color_blindness::ColorBlindness;
+pub use convert::{ConvertColor, Hsl, Hsv, Lab, Xyz};
+pub use gamut::GamutMapping;
+#[cfg(feature = "alloc")]
+pub use gradient::{Easing, Gradient, GradientBuilder, GradientSpace};
+pub use hashable::LinearColorBits;
+#[cfg(feature = "rand")]
+pub use hsl::HueRange;
+pub use hsl::{HueCycler, HuePath};
+#[cfg(feature = "alloc")]
+pub use lut3d::{Lut3d, ParseCubeError};
+#[doc(hidden)]
+pub use macros::parse_hex_literal as __parse_hex_literal;
+pub use oklab::{Oklab, Oklch};
+pub use palette::{ChannelWeights, Palette, PalettedColor};
+pub use parse::ParseColorError;
+#[cfg(feature = "alloc")]
+pub use parse::ParsePaletteError;
+pub use rgb::EncodedRgb;
+pub use theme::TONAL_PALETTE_LIGHTNESS;
+pub use tonemap::Tonemap;
+
 /// A color used in linear applications. On a technical level,
 /// this color is in sRGB; however, this name is not very clear.
 ///
@@ -40,7 +110,28 @@ impl Default for EncodedColor {
     }
 }
 
+/// One of the four channels of a color, for describing layouts like
+/// [EncodedColor::LAYOUT] and [LinearColor::LAYOUT].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Channel {
+    /// The red channel.
+    R,
+    /// The green channel.
+    G,
+    /// The blue channel.
+    B,
+    /// The alpha channel.
+    A,
+}
+
 impl EncodedColor {
+    /// The number of channels in this color, for generic pixel buffer code.
+    pub const CHANNELS: usize = 4;
+
+    /// The order of the channels in memory, one byte each. This is `[R, G, B, A]`, which is the
+    /// same on every platform.
+    pub const LAYOUT: [Channel; 4] = [Channel::R, Channel::G, Channel::B, Channel::A];
+
     /// A basic white (255, 255, 255, 255) with full opacity.
     pub const WHITE: EncodedColor = EncodedColor::new(255, 255, 255, 255);
 
@@ -90,6 +181,32 @@ impl EncodedColor {
         }
     }
 
+    /// Returns the alpha of this color as a normalized f32, from 0.0 to 1.0.
+    ///
+    /// Alpha isn't encoded by the sRGB transfer function, so this is the same alpha
+    /// that [to_linear](Self::to_linear) would give you.
+    #[inline]
+    pub fn alpha_f32(self) -> f32 {
+        self.a as f32 / 255.0
+    }
+
+    /// Multiplies this color's alpha by `factor`, rounding to the nearest step, such as
+    /// `fade(0.5)` to make it half as opaque. The result is clamped, so a factor above 1.0
+    /// can make a color more opaque, but never past 255.
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn fade(self, factor: f32) -> Self {
+        self.with_a(((self.a as f32 * factor).clamp(0.0, 255.0) + 0.5) as u8)
+    }
+
+    /// Sets this color's alpha from an opacity from 0.0 (transparent) to 1.0 (opaque),
+    /// clamping it and rounding to the nearest step.
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with_a((opacity.clamp(0.0, 1.0) * 255.0 + 0.5) as u8)
+    }
+
     /// Converts this color to an [f32; 4] array. This is **still in encoded
     /// space** but they are converted to an f32. This is mostly for compatability
     /// with other libraries which sometimes need to f32s even while in encoded sRGB.
@@ -107,6 +224,13 @@ impl EncodedColor {
         ]
     }
 
+    /// Like [to_encoded_f32s](Self::to_encoded_f32s), but drops alpha, for shader uniforms which
+    /// only want `r, g, b`. This is **still in encoded space**.
+    #[inline]
+    pub fn to_encoded_rgb_f32s(self) -> [f32; 3] {
+        [self.r as f32 / 255.0, self.g as f32 / 255.0, self.b as f32 / 255.0]
+    }
+
     /// Converts this color to an [f32; 4] array. This is **still in encoded
     /// space** but they are converted to an f32. This is mostly for compatability
     /// with other libraries which sometimes need to f32s even while in encoded sRGB.
@@ -124,13 +248,45 @@ impl EncodedColor {
         )
     }
 
+    /// Creates a color from any four numbers which are normalized from 0 to 1, in `r, g, b, a`
+    /// order, like the `[f64; 4]` colors some other libraries use. Like
+    /// [from_encoded_f32s](Self::from_encoded_f32s), these must **already be encoded**.
+    ///
+    /// Each value is clamped to 0 to 1, and then rounded to the nearest step.
+    pub fn from_normalized<T: Into<f64>>(vals: [T; 4]) -> Self {
+        let [r, g, b, a] = vals.map(|v| (v.into().clamp(0.0, 1.0) * 255.0 + 0.5) as u8);
+
+        Self::new(r, g, b, a)
+    }
+
+    /// Creates a color from `r, g, b, a` floats in the 0 to 255 range, rather than 0 to 1, like
+    /// the colors some JavaScript and CSS interop layers pass around. These must **already be
+    /// encoded**.
+    ///
+    /// Each value is clamped to 0 to 255, and then rounded to the nearest integer. Passing 0 to
+    /// 1 values here by mistake gives a nearly black, nearly clear color, so watch out for that.
+    pub fn from_rgba255_f32(input: [f32; 4]) -> Self {
+        let [r, g, b, a] = input.map(|v| (v.clamp(0.0, 255.0) + 0.5) as u8);
+
+        Self::new(r, g, b, a)
+    }
+
+    /// Converts this color to `r, g, b, a` floats in the 0 to 255 range, rather than 0 to 1, for
+    /// the JavaScript and CSS interop layers which want that. This is **still in encoded
+    /// space**. The inverse of [from_rgba255_f32](Self::from_rgba255_f32).
+    pub fn to_rgba255_f32(self) -> [f32; 4] {
+        [self.r as f32, self.g as f32, self.b as f32, self.a as f32]
+    }
+
     /// Converts a packed u32 to an encoded rgba struct.
     ///
     /// Note, your colors must be in order of `red, green, blue, alpha`. For `bgra` support,
     /// use `from_bgra_u32`.
     ///
-    /// This function might also has issues on non-little endian platforms, but look, you're not
-    /// on one of those.
+    /// This unpacks the u32's bytes in native endian order, so it only reads `0xRRGGBBAA` right
+    /// on little endian platforms. For bytes from files and the network, use
+    /// [from_be_bytes](Self::from_be_bytes) or [from_le_bytes](Self::from_le_bytes), which are
+    /// the same everywhere.
     #[inline]
     pub const fn from_rgba_u32(input: u32) -> Self {
         let bytes = input.to_ne_bytes();
@@ -148,8 +304,9 @@ impl EncodedColor {
     /// This will output your colors in order of `red, green, blue, alpha`. For `bgra` support,
     /// use `to_bgra_u32`.
     ///
-    /// This function might also have issues on non-little endian platforms, but look, you're not
-    /// on one of those.
+    /// This packs the bytes in native endian order, so the result is only `0xRRGGBBAA` on little
+    /// endian platforms. For files and the network, use [to_be_bytes](Self::to_be_bytes) or
+    /// [to_le_bytes](Self::to_le_bytes), which are the same everywhere.
     #[inline]
     pub const fn to_rgba_u32(self) -> u32 {
         let mut bytes = [0, 0, 0, 0];
@@ -162,6 +319,38 @@ impl EncodedColor {
         u32::from_ne_bytes(bytes)
     }
 
+    /// The bytes of this color's `0xRRGGBBAA` value, most significant first, like
+    /// [u32::to_be_bytes]. That's `[r, g, b, a]`, the same as the field order, on every
+    /// platform.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// The bytes of this color's `0xRRGGBBAA` value, least significant first, like
+    /// [u32::to_le_bytes]. That's `[a, b, g, r]`, on every platform, which is how little endian
+    /// file formats store a `0xRRGGBBAA` value.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 4] {
+        [self.a, self.b, self.g, self.r]
+    }
+
+    /// Creates a color from `[r, g, b, a]` bytes, made by [to_be_bytes](Self::to_be_bytes).
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 4]) -> Self {
+        let [r, g, b, a] = bytes;
+
+        Self { r, g, b, a }
+    }
+
+    /// Creates a color from `[a, b, g, r]` bytes, made by [to_le_bytes](Self::to_le_bytes).
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 4]) -> Self {
+        let [a, b, g, r] = bytes;
+
+        Self { r, g, b, a }
+    }
+
     /// Converts a packed u32 to an encoded rgba struct. On little endian platforms, this is a
     /// no-op.
     ///
@@ -200,6 +389,118 @@ impl EncodedColor {
         u32::from_ne_bytes(bytes)
     }
 
+    /// Converts a packed `0xAARRGGBB` u32 to an encoded rgba struct. This is the layout many
+    /// Windows APIs (like GDI and Direct2D) use.
+    ///
+    /// Unlike the `rgba` and `bgra` functions, this uses shifts, so it works the same on every
+    /// platform.
+    #[inline]
+    pub const fn from_argb_u32(input: u32) -> Self {
+        Self {
+            r: (input >> 16) as u8,
+            g: (input >> 8) as u8,
+            b: input as u8,
+            a: (input >> 24) as u8,
+        }
+    }
+
+    /// Converts the encoded rgba struct to a packed `0xAARRGGBB` u32. This is the layout many
+    /// Windows APIs (like GDI and Direct2D) use.
+    ///
+    /// Unlike the `rgba` and `bgra` functions, this uses shifts, so it works the same on every
+    /// platform.
+    #[inline]
+    pub const fn to_argb_u32(self) -> u32 {
+        (self.a as u32) << 24 | (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+
+    /// Pulls a single channel out of a packed `0xRRGGBBAA` u32, without building the whole
+    /// color, for const code like table generation. `index` is 0 for red, 1 for green, 2 for
+    /// blue, and 3 for alpha, like in [LAYOUT](Self::LAYOUT).
+    ///
+    /// This uses shifts, so it works the same on every platform. On little endian platforms,
+    /// it agrees with [from_rgba_u32](Self::from_rgba_u32).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 3. In a const, that's a compile error.
+    pub const fn channel_from_rgba_u32(input: u32, index: usize) -> u8 {
+        assert!(index < 4, "channel index must be 0, 1, 2, or 3");
+
+        (input >> (24 - 8 * index)) as u8
+    }
+
+    /// Pulls a single channel out of a packed u32 in the layout of
+    /// [from_bgra_u32](Self::from_bgra_u32) on little endian platforms, which is `0xBBGGRRAA`.
+    /// `index` is 0 for red, 1 for green, 2 for blue, and 3 for alpha.
+    ///
+    /// Like [channel_from_rgba_u32](Self::channel_from_rgba_u32), this uses shifts, so it
+    /// works the same on every platform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 3. In a const, that's a compile error.
+    pub const fn channel_from_bgra_u32(input: u32, index: usize) -> u8 {
+        const SHIFTS: [u32; 4] = [8, 16, 24, 0];
+        assert!(index < 4, "channel index must be 0, 1, 2, or 3");
+
+        (input >> SHIFTS[index]) as u8
+    }
+
+    /// Pulls a single channel out of a packed `0xAARRGGBB` u32, the layout of
+    /// [from_argb_u32](Self::from_argb_u32). `index` is 0 for red, 1 for green, 2 for blue,
+    /// and 3 for alpha.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 3. In a const, that's a compile error.
+    pub const fn channel_from_argb_u32(input: u32, index: usize) -> u8 {
+        const SHIFTS: [u32; 4] = [16, 8, 0, 24];
+        assert!(index < 4, "channel index must be 0, 1, 2, or 3");
+
+        (input >> SHIFTS[index]) as u8
+    }
+
+    /// Reorders the channels of this color. Each entry in `order` is the index (0 for red,
+    /// 1 for green, 2 for blue, and 3 for alpha) of the channel to put in that position, so
+    /// `[2, 1, 0, 3]` swaps red and blue.
+    ///
+    /// This is for converting between byte layouts, so the fields of the output no longer hold
+    /// the channels they are named after.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is greater than 3.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn swizzle(self, order: [usize; 4]) -> Self {
+        let channels = [self.r, self.g, self.b, self.a];
+
+        Self {
+            r: channels[order[0]],
+            g: channels[order[1]],
+            b: channels[order[2]],
+            a: channels[order[3]],
+        }
+    }
+
+    /// Reorders the channels into `bgra` order. See [swizzle](Self::swizzle).
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn to_bgra(self) -> Self {
+        self.swizzle([2, 1, 0, 3])
+    }
+
+    /// Reorders the channels into `argb` order. See [swizzle](Self::swizzle).
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn to_argb(self) -> Self {
+        self.swizzle([3, 0, 1, 2])
+    }
+
+    /// Reorders the channels into `abgr` order. See [swizzle](Self::swizzle).
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub const fn to_abgr(self) -> Self {
+        self.swizzle([3, 2, 1, 0])
+    }
+
     /// Recasts four u8s into `EncodedColor`
     pub const fn from_bits_u32(value: u32) -> Self {
         unsafe { core::mem::transmute(value) }
@@ -345,6 +646,14 @@ pub struct LinearColor {
 }
 
 impl LinearColor {
+    /// The number of channels in this color, for generic pixel buffer code.
+    pub const CHANNELS: usize = 4;
+
+    /// The order of the channels in [to_array](Self::to_array) and [to_bits](Self::to_bits), one
+    /// f32 each. This is `[R, G, B, A]`, though in `to_bits` the bytes of each f32 are in the
+    /// platform's byte order.
+    pub const LAYOUT: [Channel; 4] = [Channel::R, Channel::G, Channel::B, Channel::A];
+
     /// **You probably don't want to use this function.**
     /// This creates a color in the LinearColor space directly. For this function to be valid,
     /// the colors given to this function **must be in the linear space already.**
@@ -353,6 +662,23 @@ impl LinearColor {
         Self { r, g, b, a }
     }
 
+    /// Creates a color from absolute luminance, in nits (cd/m²), for HDR work.
+    ///
+    /// Each channel is divided by `reference_white_nits`, so a channel as bright as reference
+    /// white is 1.0, and brighter channels go above 1.0. Common choices for reference white are
+    /// 80 nits (the sRGB spec), or 203 nits (BT.2408, for HDR video).
+    #[inline]
+    pub fn from_nits(rgb_nits: [f32; 3], a: f32, reference_white_nits: f32) -> Self {
+        let [r, g, b] = rgb_nits;
+
+        Self::new(
+            r / reference_white_nits,
+            g / reference_white_nits,
+            b / reference_white_nits,
+            a,
+        )
+    }
+
     /// Transforms this color into the Encoded color space. Use this space to serialize
     /// colors.
     #[inline]
@@ -365,6 +691,64 @@ impl LinearColor {
         }
     }
 
+    /// Transforms this color into the Encoded color space, like
+    /// [to_encoded_space](Self::to_encoded_space), and also reports whether any channel
+    /// (including alpha) was outside of 0.0 to 1.0 and had to be clamped. NaNs count as clamped.
+    ///
+    /// This is handy for tracking down where HDR values are leaking into an SDR path.
+    #[inline]
+    pub fn to_encoded_checked(self) -> (EncodedColor, bool) {
+        let clamped = [self.r, self.g, self.b, self.a]
+            .iter()
+            .any(|c| !(0.0..=1.0).contains(c));
+
+        (self.to_encoded_space(), clamped)
+    }
+
+    /// Snaps this color onto the closest color an [EncodedColor] can hold, by encoding it and
+    /// decoding it again. Alpha is snapped to the nearest of its 256 steps too.
+    ///
+    /// This is for simulating 8-bit storage, or previewing the banding it causes, in an
+    /// otherwise float pipeline. Requantizing an already requantized color does nothing.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    #[inline]
+    pub fn requantize(self) -> LinearColor {
+        self.to_encoded_space().with_a(self.alpha_u8()).to_linear()
+    }
+
+    /// Returns the alpha of this color as a u8, clamping it to 0.0 to 1.0 first and rounding
+    /// to the nearest step.
+    #[inline]
+    pub fn alpha_u8(self) -> u8 {
+        (self.a.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+    }
+
+    /// Returns the [EncodedColor] this color came from, if it's exactly (within a tiny epsilon)
+    /// the linear version of one. Otherwise, this returns `None`.
+    ///
+    /// This is useful for skipping work, like dithering, on colors which will encode losslessly
+    /// anyway, such as colors which came straight from [to_linear](EncodedColor::to_linear).
+    pub fn nearest_encoded_exact(self) -> Option<EncodedColor> {
+        const EPSILON: f32 = 1e-6;
+
+        let channel = |c: f32| {
+            let encoded = linear_to_encoded(c);
+            ((encoded_to_linear(encoded) - c).abs() <= EPSILON).then_some(encoded)
+        };
+
+        let a = self.alpha_u8();
+        if (a as f32 / 255.0 - self.a).abs() > EPSILON {
+            return None;
+        }
+
+        Some(EncodedColor::new(
+            channel(self.r)?,
+            channel(self.g)?,
+            channel(self.b)?,
+            a,
+        ))
+    }
+
     /// Creates an array representation of the color. This is useful for sending the color
     /// to a uniform, but is the same memory representation as `Self`. [LinearColor] also implements
     /// Into, but this function is often more convenient.
@@ -373,9 +757,30 @@ impl LinearColor {
         self.into()
     }
 
+    /// Like [to_array](Self::to_array), but drops alpha, for shader uniforms which only want
+    /// `r, g, b`.
+    #[inline]
+    pub fn to_rgb_array(self) -> [f32; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    /// Yields the 256 encoded gray steps, from black to white, as opaque linear colors. Each
+    /// channel is the matching entry of [ENCODED_TO_LINEAR_LUT].
+    ///
+    /// This is for checking that a pipeline reproduces the sRGB curve, like by rendering these
+    /// through a shader and comparing the results with [linear_to_encoded].
+    pub fn srgb_ramp() -> impl Iterator<Item = LinearColor> {
+        ENCODED_TO_LINEAR_LUT
+            .into_iter()
+            .map(|c| LinearColor::new(c, c, c, 1.0))
+    }
+
     /// Encodes the 4 floats as 16 u8s. This is useful for sending the color
     /// to a uniform, but is the same memory representation as `Self` -- ie,
     /// the bits have just been reinterpreted as 16 u8s, but they're still secret floats.
+    ///
+    /// The floats are in the platform's native byte order. For files or the network, use
+    /// [to_le_bytes](Self::to_le_bytes) or [to_be_bytes](Self::to_be_bytes) instead.
     #[inline]
     pub fn to_bits(self) -> [u8; 16] {
         unsafe { core::mem::transmute(self.to_array()) }
@@ -386,6 +791,70 @@ impl LinearColor {
     pub fn from_bits(value: [u8; 16]) -> Self {
         unsafe { core::mem::transmute(value) }
     }
+
+    /// Encodes the 4 floats as 16 u8s, in `r, g, b, a` order, with each float in little endian
+    /// byte order no matter the platform. This is the portable version of
+    /// [to_bits](Self::to_bits), for file formats and the like.
+    #[inline]
+    pub fn to_le_bytes(self) -> [u8; 16] {
+        self.to_bytes_with(f32::to_le_bytes)
+    }
+
+    /// Encodes the 4 floats as 16 u8s, in `r, g, b, a` order, with each float in big endian
+    /// byte order no matter the platform.
+    #[inline]
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        self.to_bytes_with(f32::to_be_bytes)
+    }
+
+    /// Decodes 16 u8s made by [to_le_bytes](Self::to_le_bytes).
+    #[inline]
+    pub fn from_le_bytes(value: [u8; 16]) -> Self {
+        Self::from_bytes_with(value, f32::from_le_bytes)
+    }
+
+    /// Decodes 16 u8s made by [to_be_bytes](Self::to_be_bytes).
+    #[inline]
+    pub fn from_be_bytes(value: [u8; 16]) -> Self {
+        Self::from_bytes_with(value, f32::from_be_bytes)
+    }
+
+    fn to_bytes_with(self, f: fn(f32) -> [u8; 4]) -> [u8; 16] {
+        let mut output = [0; 16];
+        for (chunk, channel) in output.chunks_exact_mut(4).zip(self.to_array()) {
+            chunk.copy_from_slice(&f(channel));
+        }
+
+        output
+    }
+
+    fn from_bytes_with(value: [u8; 16], f: fn([u8; 4]) -> f32) -> Self {
+        let channel = |i: usize| f([value[i], value[i + 1], value[i + 2], value[i + 3]]);
+
+        Self::new(channel(0), channel(4), channel(8), channel(12))
+    }
+
+    /// Rounds each channel to the given number of decimal places. This is mostly useful right
+    /// before serializing into a text format, where full f32 precision is noisy and large.
+    ///
+    /// f32s only hold about 7 significant digits, so asking for more than 8 decimals gives back
+    /// the color unchanged.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn round_to(self, decimals: u32) -> Self {
+        if decimals > 8 {
+            return self;
+        }
+
+        let factor = 10u32.pow(decimals) as f32;
+        let round = |c: f32| math::round(c * factor) / factor;
+
+        Self {
+            r: round(self.r),
+            g: round(self.g),
+            b: round(self.b),
+            a: round(self.a),
+        }
+    }
 }
 
 impl From<LinearColor> for [f32; 4] {
@@ -441,6 +910,46 @@ impl From<EncodedColor> for LinearColor {
     }
 }
 
+/// The offset in the curved part of the sRGB transfer function, often called `a` or `alpha`.
+/// The curve is `(1 + SRGB_ALPHA) * linear^(1 / SRGB_GAMMA) - SRGB_ALPHA`.
+pub const SRGB_ALPHA: f32 = 0.055;
+
+/// The exponent of the curved part of the sRGB transfer function. Note that this is *not*
+/// the "gamma 2.2" which sRGB is often approximated as -- the linear segment near black makes
+/// the overall curve close to 2.2.
+pub const SRGB_GAMMA: f32 = 2.4;
+
+/// The slope of the linear segment of the sRGB transfer function, near black.
+pub const SRGB_LINEAR_SLOPE: f32 = 12.92;
+
+/// Linear values below this use the linear segment of the sRGB transfer function when encoding.
+pub const SRGB_LINEAR_THRESHOLD: f32 = 0.0031308;
+
+/// Encoded values below this use the linear segment of the sRGB transfer function when
+/// decoding. This is [SRGB_LINEAR_THRESHOLD] times [SRGB_LINEAR_SLOPE], rounded.
+pub const SRGB_ENCODED_THRESHOLD: f32 = 0.04045;
+
+/// The most any channel can change when an [EncodedColor] goes to linear and back, as in
+/// `color.to_linear().to_encoded_space()`.
+///
+/// This is 0: every encoded color survives the round trip exactly, with or without the
+/// `decode-lut` feature. Going the other way, from [LinearColor] to encoded and back, loses
+/// precision, since many linear values share each encoded value.
+pub const MAX_ENCODE_ROUNDTRIP_ERROR: u8 = 0;
+
+/// How far the linear value of `c` drifts, in linear space, when it's encoded to a float and
+/// decoded again, as in `encoded_f32_to_linear(linear_to_encoded_f32(encoded_to_linear(c)))`
+/// (with the unquantized encoding [linear_to_encoded] uses inside).
+///
+/// This is the float precision of the transfer functions alone, with no u8 rounding involved,
+/// for checking whether a pipeline which keeps encoded values as floats can rely on them. It's
+/// always below `1e-6`, for every `c`, and smaller with the `high-precision` feature.
+pub fn roundtrip_error(c: u8) -> f32 {
+    let linear = encoded_to_linear(c);
+
+    (encoded_f32_to_linear(linear_to_encoded_f32(linear)) - linear).abs()
+}
+
 /// This function takes an encoded u8 and outputs a linear space (linear) sRgb f32.
 ///
 /// This is based on <https://bottosson.github.io/posts/colorwrong/> and similar
@@ -478,6 +987,47 @@ pub const fn encoded_to_linear(c: u8) -> f32 {
     // Thank you very much to @thomcc (@zurr on discord) for helping me with this!
 }
 
+/// This function takes an encoded value from 0.0 to 1.0 and outputs a linear space f32. This is
+/// the same transfer function as [encoded_to_linear], but on a float, computed live.
+///
+/// This is for encoded values with more precision than a u8, like 16 bit textures, which would
+/// lose precision going through [encoded_to_linear]. Values outside of 0.0 to 1.0 aren't
+/// clamped; negative values use the linear segment.
+///
+/// With the `high-precision` feature, the math is done in f64.
+pub fn encoded_f32_to_linear(input: f32) -> f32 {
+    #[cfg(feature = "high-precision")]
+    {
+        decode_f64(input as f64) as f32
+    }
+
+    #[cfg(not(feature = "high-precision"))]
+    {
+        decode_f32(input)
+    }
+}
+
+/// The decoding transfer function, in f32.
+#[cfg_attr(feature = "high-precision", allow(dead_code))]
+fn decode_f32(input: f32) -> f32 {
+    if input >= SRGB_ENCODED_THRESHOLD {
+        math::powf((input + SRGB_ALPHA) / (1.0 + SRGB_ALPHA), SRGB_GAMMA)
+    } else {
+        input / SRGB_LINEAR_SLOPE
+    }
+}
+
+/// The decoding transfer function, in f64, for the `high-precision` feature.
+#[cfg_attr(not(feature = "high-precision"), allow(dead_code))]
+fn decode_f64(input: f64) -> f64 {
+    // the f32 constants aren't exactly these values, so we can't just cast them up
+    if input >= 0.04045 {
+        math::powf64((input + 0.055) / 1.055, 2.4)
+    } else {
+        input / 12.92
+    }
+}
+
 /// This is the LUT that we use. You shouldn't really ever need to use directly, but `encoded_to_linear`
 /// is just a wrapper to index into this LUT.
 /// 
@@ -522,25 +1072,174 @@ pub const ENCODED_TO_LINEAR_LUT: [f32; 256] = [
 ///
 /// This is based on <https://bottosson.github.io/posts/colorwrong/> and similar
 /// transfer functions.
+///
+/// By default, this computes the transfer function live with `powf`. Different `powf`
+/// implementations can disagree in the last bit, which occasionally shows up as an off-by-one
+/// in the output. If you need the output to be bit-identical across platforms, enable the
+/// `decode-lut` feature, and this will use [linear_to_encoded_lut] instead.
+///
+/// This quantizes with [Rounding::EqualBins]. For other modes, see [linear_to_encoded_with].
 pub fn linear_to_encoded(input: f32) -> u8 {
-    #[cfg(feature = "libm")]
-    use libm::powf;
+    #[cfg(feature = "decode-lut")]
+    {
+        linear_to_encoded_lut(input)
+    }
 
-    #[cfg(feature = "std")]
-    fn powf(f: f32, e: f32) -> f32 {
-        f.powf(e)
+    #[cfg(not(feature = "decode-lut"))]
+    {
+        linear_to_encoded_live(input)
     }
+}
 
-    let encoded_f32 = if input >= 0.0031308 {
-        1.055 * powf(input, 1.0 / 2.4) - 0.055
-    } else {
-        12.92 * input
-    };
+/// How [linear_to_encoded_with] turns an encoded value from 0.0 to 1.0 into a u8. Tools don't
+/// agree on this, so when you need to match one exactly, pick its mode here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Splits 0.0 to 1.0 into 256 equally wide steps, so this is `floor(value * 256)`, with
+    /// 1.0 going to 255. This is what [linear_to_encoded] does, and what the rest of this
+    /// crate uses.
+    #[default]
+    EqualBins,
+
+    /// Rounds `value * 255` down. The transfer function isn't perfectly precise, so this
+    /// sometimes gives one less than the exact value, even for linear values which came from
+    /// [encoded_to_linear].
+    Truncate,
+
+    /// Rounds `value * 255` to the nearest integer, with halves going up.
+    Nearest,
+
+    /// Rounds `value * 255` to the nearest integer, with halves going to the even one, like
+    /// IEEE 754's default. This avoids a slight upward bias over many values.
+    NearestEven,
+}
+
+impl Rounding {
+    fn quantize(self, encoded: f32) -> u8 {
+        // NaN fails every comparison, so it ends up as 0 in each of these.
+        let x = (encoded * 255.0).clamp(0.0, 255.0);
+
+        match self {
+            Rounding::EqualBins => (encoded * 256.0) as u8,
+            Rounding::Truncate => x as u8,
+            Rounding::Nearest => (x + 0.5) as u8,
+            Rounding::NearestEven => {
+                let floor = x as u8;
+                let fraction = x - floor as f32;
+
+                if fraction > 0.5 || (fraction == 0.5 && floor % 2 == 1) {
+                    floor + 1
+                } else {
+                    floor
+                }
+            }
+        }
+    }
+}
+
+/// Like [linear_to_encoded], but with the given [Rounding], for matching another implementation
+/// exactly. With [Rounding::EqualBins], this is the same as [linear_to_encoded].
+pub fn linear_to_encoded_with(input: f32, rounding: Rounding) -> u8 {
+    match rounding {
+        Rounding::EqualBins => linear_to_encoded(input),
+        _ => rounding.quantize(linear_to_encoded_f32(input)),
+    }
+}
 
+/// This function takes a linear space f32 and outputs an encoded sRgb u8 by searching
+/// [LINEAR_TO_ENCODED_LUT], rather than computing the transfer function.
+///
+/// Since this is only a handful of float comparisons, its output is identical on every
+/// platform. It agrees with the live math within one step (and almost always exactly).
+#[inline]
+pub fn linear_to_encoded_lut(input: f32) -> u8 {
+    // NaN compares false against everything, so it ends up at 0, like the live path.
+    LINEAR_TO_ENCODED_LUT.partition_point(|&threshold| threshold <= input) as u8
+}
+
+/// This is the LUT used by [linear_to_encoded_lut]. Entry `i` is the smallest linear value
+/// which encodes to `i + 1`, so the encoded value of an input is the number of entries
+/// it is greater than or equal to.
+///
+/// Like [ENCODED_TO_LINEAR_LUT], this was computed with f64s and written inline.
+#[rustfmt::skip]
+pub const LINEAR_TO_ENCODED_LUT: [f32; 255] = [
+    0.00030234133, 0.00060468266, 0.000907024, 0.0012093653, 0.0015117066, 0.001814048, 0.0021163893,
+    0.0024187306, 0.002721072, 0.0030234132, 0.0033327616, 0.0036606346, 0.0040065907, 0.0043709036,
+    0.004753842, 0.0051556686, 0.00557664, 0.006017009, 0.006477022, 0.006956923, 0.007456949,
+    0.007977336, 0.008518311, 0.0090801045, 0.009662936, 0.010267027, 0.010892591, 0.011539844,
+    0.012208993, 0.012900245, 0.013613806, 0.014349875, 0.015108651, 0.015890328, 0.016695103,
+    0.017523166, 0.018374702, 0.019249901, 0.020148948, 0.021072023, 0.022019306, 0.022990976,
+    0.023987208, 0.025008177, 0.026054056, 0.027125016, 0.028221224, 0.029342849, 0.030490056,
+    0.031663008, 0.03286187, 0.0340868, 0.035337962, 0.03661551, 0.037919603, 0.039250392, 0.040608037,
+    0.041992687, 0.04340449, 0.044843607, 0.046310175, 0.04780435, 0.04932627, 0.05087609, 0.052453946,
+    0.054059986, 0.055694353, 0.057357185, 0.05904862, 0.0607688, 0.06251787, 0.06429595, 0.06610318,
+    0.06793971, 0.06980567, 0.07170118, 0.07362638, 0.0755814, 0.07756637, 0.079581425, 0.08162669,
+    0.083702296, 0.08580837, 0.08794502, 0.0901124, 0.09231062, 0.09453981, 0.09680009, 0.09909158,
+    0.101414405, 0.10376869, 0.106154546, 0.1085721, 0.111021474, 0.11350278, 0.116016135, 0.11856166,
+    0.121139474, 0.12374968, 0.12639241, 0.12906778, 0.13177589, 0.13451685, 0.13729079, 0.14009781,
+    0.14293803, 0.14581156, 0.1487185, 0.15165898, 0.1546331, 0.15764096, 0.16068268, 0.16375835,
+    0.1668681, 0.17001204, 0.17319025, 0.17640285, 0.17964996, 0.18293166, 0.18624806, 0.18959929,
+    0.19298542, 0.19640657, 0.19986284, 0.20335433, 0.20688114, 0.21044338, 0.21404114, 0.21767452,
+    0.22134365, 0.22504859, 0.22878945, 0.23256634, 0.23637936, 0.2402286, 0.24411415, 0.24803612,
+    0.2519946, 0.2559897, 0.26002148, 0.2640901, 0.26819557, 0.27233806, 0.27651763, 0.28073436,
+    0.28498837, 0.2892798, 0.29360864, 0.29797503, 0.30237907, 0.30682087, 0.3113005, 0.315818,
+    0.32037356, 0.3249672, 0.32959905, 0.3342692, 0.3389777, 0.34372467, 0.3485102, 0.35333437,
+    0.35819724, 0.36309898, 0.36803958, 0.3730192, 0.3780379, 0.38309577, 0.3881929, 0.39332935,
+    0.39850524, 0.40372065, 0.40897566, 0.41427034, 0.4196048, 0.42497912, 0.4303934, 0.43584767,
+    0.4413421, 0.44687667, 0.45245153, 0.45806676, 0.4637224, 0.46941862, 0.4751554, 0.48093292,
+    0.48675117, 0.4926103, 0.49851036, 0.5044514, 0.5104336, 0.51645696, 0.52252156, 0.5286275,
+    0.5347749, 0.54096377, 0.5471942, 0.5534663, 0.5597802, 0.5661358, 0.57253337, 0.57897294,
+    0.5854545, 0.59197825, 0.5985442, 0.60515237, 0.61180294, 0.61849594, 0.6252315, 0.6320096,
+    0.6388304, 0.645694, 0.6526004, 0.65954965, 0.66654193, 0.67357725, 0.68065566, 0.6877773,
+    0.69494224, 0.70215046, 0.7094022, 0.7166974, 0.72403616, 0.73141855, 0.7388447, 0.74631464,
+    0.75382847, 0.7613862, 0.768988, 0.77663386, 0.7843239, 0.79205817, 0.79983675, 0.8076597,
+    0.8155271, 0.823439, 0.8313955, 0.8393967, 0.8474426, 0.85553336, 0.863669, 0.8718496, 0.88007516,
+    0.88834584, 0.8966617, 0.9050228, 0.91342914, 0.9218809, 0.93037814, 0.93892086, 0.9475091,
+    0.9561431, 0.96482277, 0.97354823, 0.98231953, 0.9911368,
+];
+
+#[cfg_attr(feature = "decode-lut", allow(dead_code))]
+pub(crate) fn linear_to_encoded_live(input: f32) -> u8 {
     // this multiply to 256 is VERY odd! but otherwise,
     // 1.0 cannot translate to 1.0. Weirdly, this seems fine actually
     // in tests.
-    (encoded_f32 * 256.0) as u8
+    Rounding::EqualBins.quantize(linear_to_encoded_f32(input))
+}
+
+/// The encoding transfer function, without quantizing to a u8. With the `high-precision`
+/// feature, the math is done in f64.
+pub(crate) fn linear_to_encoded_f32(input: f32) -> f32 {
+    #[cfg(feature = "high-precision")]
+    {
+        encode_f64(input as f64) as f32
+    }
+
+    #[cfg(not(feature = "high-precision"))]
+    {
+        encode_f32(input)
+    }
+}
+
+/// The encoding transfer function, in f32.
+#[cfg_attr(feature = "high-precision", allow(dead_code))]
+fn encode_f32(input: f32) -> f32 {
+    if input >= SRGB_LINEAR_THRESHOLD {
+        (1.0 + SRGB_ALPHA) * math::powf(input, 1.0 / SRGB_GAMMA) - SRGB_ALPHA
+    } else {
+        SRGB_LINEAR_SLOPE * input
+    }
+}
+
+/// The encoding transfer function, in f64, for the `high-precision` feature.
+#[cfg_attr(not(feature = "high-precision"), allow(dead_code))]
+fn encode_f64(input: f64) -> f64 {
+    // the f32 constants aren't exactly these values, so we can't just cast them up
+    if input >= 0.0031308 {
+        1.055 * math::powf64(input, 1.0 / 2.4) - 0.055
+    } else {
+        12.92 * input
+    }
 }
 
 #[cfg(feature = "bytemuck")]
@@ -641,12 +1340,78 @@ impl rand::distributions::Distribution<LinearColor> for rand::distributions::Sta
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for EncodedColor {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(EncodedColor {
+            r: u.arbitrary()?,
+            g: u.arbitrary()?,
+            b: u.arbitrary()?,
+            a: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (4, Some(4))
+    }
+}
+
+/// Note that this can make any f32s at all, including NaNs and infinities.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for LinearColor {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(LinearColor {
+            r: u.arbitrary()?,
+            g: u.arbitrary()?,
+            b: u.arbitrary()?,
+            a: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (16, Some(16))
+    }
+}
+
+/// Whether every channel of `a` and `b`, including alpha, is within float error of the other.
+/// This is shared by the tests across the crate.
+#[cfg(test)]
+pub(crate) fn close(a: LinearColor, b: LinearColor) -> bool {
+    let a: [f32; 4] = a.into();
+    let b: [f32; 4] = b.into();
+
+    a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() < 1e-5)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     static_assertions::assert_eq_align!(EncodedColor, u8);
     static_assertions::assert_eq_size!(EncodedColor, [u8; 4]);
+    static_assertions::const_assert_eq!(EncodedColor::CHANNELS, core::mem::size_of::<EncodedColor>());
+    static_assertions::const_assert_eq!(EncodedColor::CHANNELS, EncodedColor::LAYOUT.len());
+    static_assertions::const_assert_eq!(LinearColor::CHANNELS * 4, core::mem::size_of::<LinearColor>());
+    static_assertions::const_assert_eq!(LinearColor::CHANNELS, LinearColor::LAYOUT.len());
+
+    #[test]
+    fn layout() {
+        let color = EncodedColor::new(1, 2, 3, 4);
+        // SAFETY: EncodedColor is `repr(C)` and four bytes, with no padding.
+        let bytes: [u8; 4] = unsafe { core::mem::transmute(color) };
+        for (i, channel) in EncodedColor::LAYOUT.iter().enumerate() {
+            let expected = match channel {
+                Channel::R => color.r,
+                Channel::G => color.g,
+                Channel::B => color.b,
+                Channel::A => color.a,
+            };
+            assert_eq!(bytes[i], expected);
+        }
+
+        let color = LinearColor::new(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(color.to_array(), [color.r, color.g, color.b, color.a]);
+    }
 
     #[test]
     fn builders() {
@@ -657,6 +1422,426 @@ mod tests {
         assert_eq!(c.with_a(128), EncodedColor::new(255, 255, 255, 128));
     }
 
+    #[test]
+    fn rgb_arrays() {
+        let sky = EncodedColor::new(107, 158, 190, 128);
+        let [r, g, b, _] = sky.to_encoded_f32s();
+        assert_eq!(sky.to_encoded_rgb_f32s(), [r, g, b]);
+        assert_eq!(EncodedColor::WHITE.to_encoded_rgb_f32s(), [1.0; 3]);
+
+        let linear = LinearColor::new(0.25, 0.5, 0.75, 0.1);
+        assert_eq!(linear.to_rgb_array(), [0.25, 0.5, 0.75]);
+        let [r, g, b, _] = sky.to_linear().to_array();
+        assert_eq!(sky.to_linear().to_rgb_array(), [r, g, b]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn alpha_helpers() {
+        assert_eq!(EncodedColor::CLEAR.alpha_f32(), 0.0);
+        assert!((EncodedColor::WHITE.with_a(128).alpha_f32() - 0.5019608).abs() < f32::EPSILON);
+        assert_eq!(EncodedColor::WHITE.alpha_f32(), 1.0);
+
+        let linear = LinearColor::new(1.0, 1.0, 1.0, 0.0);
+        assert_eq!(linear.alpha_u8(), 0);
+        assert_eq!(LinearColor { a: 0.5, ..linear }.alpha_u8(), 128);
+        assert_eq!(LinearColor { a: 1.0, ..linear }.alpha_u8(), 255);
+        assert_eq!(LinearColor { a: 2.0, ..linear }.alpha_u8(), 255);
+        assert_eq!(LinearColor { a: -1.0, ..linear }.alpha_u8(), 0);
+
+        for a in 0..=255 {
+            let c = EncodedColor::WHITE.with_a(a);
+            assert_eq!(c.to_linear().alpha_u8(), a);
+        }
+    }
+
+    #[test]
+    fn fade_and_opacity() {
+        let color = EncodedColor::new(107, 158, 190, 200);
+        assert_eq!(color.fade(0.5), color.with_a(100));
+        assert_eq!(color.fade(0.0), color.with_a(0));
+        assert_eq!(color.fade(1.0), color);
+        assert_eq!(color.fade(2.0), color.with_a(255));
+        assert_eq!(color.fade(-1.0), color.with_a(0));
+        assert_eq!(EncodedColor::WHITE.fade(0.5).a, 128);
+
+        assert_eq!(color.with_opacity(1.0), color.with_a(255));
+        assert_eq!(color.with_opacity(0.0), color.with_a(0));
+        assert_eq!(color.with_opacity(0.5), color.with_a(128));
+        assert_eq!(color.with_opacity(7.0), color.with_a(255));
+        assert_eq!(color.with_opacity(0.25).alpha_f32(), 64.0 / 255.0);
+    }
+
+    #[test]
+    // the bound is 0 right now, but these should still check it if that ever changes
+    #[allow(clippy::absurd_extreme_comparisons)]
+    fn max_encode_roundtrip_error() {
+        for c in 0..=255 {
+            let color = EncodedColor::new(c, 255 - c, c / 3, c);
+            let round_trip = color.to_linear().to_encoded_space();
+
+            for (a, b) in [
+                (color.r, round_trip.r),
+                (color.g, round_trip.g),
+                (color.b, round_trip.b),
+                (color.a, round_trip.a),
+            ] {
+                assert!(
+                    a.abs_diff(b) <= MAX_ENCODE_ROUNDTRIP_ERROR,
+                    "{:?} became {:?}",
+                    color,
+                    round_trip
+                );
+            }
+
+            // both ways of encoding hold to it, whichever one `linear_to_encoded` uses
+            assert!(linear_to_encoded_live(encoded_to_linear(c)).abs_diff(c) <= MAX_ENCODE_ROUNDTRIP_ERROR);
+            assert!(linear_to_encoded_lut(encoded_to_linear(c)).abs_diff(c) <= MAX_ENCODE_ROUNDTRIP_ERROR);
+        }
+    }
+
+    #[test]
+    fn roundtrip_error() {
+        let max = (0..=255).map(super::roundtrip_error).fold(0.0, f32::max);
+        assert!(max < 1e-6, "{}", max);
+
+        assert_eq!(super::roundtrip_error(0), 0.0);
+    }
+
+    #[test]
+    fn srgb_ramp() {
+        let ramp: [LinearColor; 256] = {
+            let mut ramp = LinearColor::srgb_ramp();
+            core::array::from_fn(|_| ramp.next().unwrap())
+        };
+        assert_eq!(LinearColor::srgb_ramp().count(), 256);
+
+        for (i, color) in ramp.iter().enumerate() {
+            assert_eq!(*color, EncodedColor::new(i as u8, i as u8, i as u8, 255).to_linear());
+            assert_eq!(color.r, ENCODED_TO_LINEAR_LUT[i]);
+        }
+        for pair in ramp.windows(2) {
+            assert!(pair[0].r < pair[1].r);
+        }
+        assert_eq!(ramp[0], LinearColor::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(ramp[255], LinearColor::new(1.0, 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn from_normalized() {
+        assert_eq!(
+            EncodedColor::from_normalized([0.42f64, 0.62, 0.745, 1.0]),
+            EncodedColor::new(107, 158, 190, 255)
+        );
+        assert_eq!(
+            EncodedColor::from_normalized([0.5f32, 0.0, 1.0, 0.25]),
+            EncodedColor::new(128, 0, 255, 64)
+        );
+        assert_eq!(
+            EncodedColor::from_normalized([-3.0, 2.0, f64::NAN, 1.5]),
+            EncodedColor::new(0, 255, 0, 255)
+        );
+        assert_eq!(EncodedColor::from_normalized([1i32, 0, 1, 1]), EncodedColor::FUCHSIA);
+    }
+
+    #[test]
+    fn rgba255_f32() {
+        let sky = EncodedColor::new(107, 158, 190, 255);
+        assert_eq!(sky.to_rgba255_f32(), [107.0, 158.0, 190.0, 255.0]);
+        assert_eq!(EncodedColor::from_rgba255_f32([107.0, 158.0, 190.0, 255.0]), sky);
+        assert_eq!(EncodedColor::from_rgba255_f32(sky.to_rgba255_f32()), sky);
+
+        assert_eq!(EncodedColor::from_rgba255_f32([106.6, 157.5, 190.4, 254.9]), sky);
+        assert_eq!(
+            EncodedColor::from_rgba255_f32([-3.0, 300.0, f32::NAN, 255.0]),
+            EncodedColor::new(0, 255, 0, 255)
+        );
+    }
+
+    #[test]
+    fn explicit_endian_bytes() {
+        let sky = EncodedColor::new(0x6b, 0x9e, 0xbe, 0x80);
+        assert_eq!(sky.to_be_bytes(), [0x6b, 0x9e, 0xbe, 0x80]);
+        assert_eq!(sky.to_le_bytes(), [0x80, 0xbe, 0x9e, 0x6b]);
+        assert_eq!(EncodedColor::from_be_bytes([0x6b, 0x9e, 0xbe, 0x80]), sky);
+        assert_eq!(EncodedColor::from_le_bytes([0x80, 0xbe, 0x9e, 0x6b]), sky);
+
+        // the same as the bytes of the u32, on every platform
+        assert_eq!(sky.to_be_bytes(), 0x6b9ebe80u32.to_be_bytes());
+        assert_eq!(sky.to_le_bytes(), 0x6b9ebe80u32.to_le_bytes());
+
+        // 1.0 is 0x3f800000, 0.5 is 0x3f000000, -2.0 is 0xc0000000, and 0.0 is 0.
+        let color = LinearColor::new(1.0, 0.5, -2.0, 0.0);
+
+        let le = [
+            0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let be = [
+            0x3f, 0x80, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        assert_eq!(color.to_le_bytes(), le);
+        assert_eq!(color.to_be_bytes(), be);
+        assert_eq!(LinearColor::from_le_bytes(le), color);
+        assert_eq!(LinearColor::from_be_bytes(be), color);
+
+        let color = LinearColor::new(0.147, 0.342, 0.515, 0.25);
+        assert_eq!(LinearColor::from_le_bytes(color.to_le_bytes()), color);
+        assert_eq!(LinearColor::from_be_bytes(color.to_be_bytes()), color);
+    }
+
+    #[test]
+    fn rounding_modes() {
+        // exactly on a half
+        assert_eq!(Rounding::Truncate.quantize(2.5 / 255.0), 2);
+        assert_eq!(Rounding::Nearest.quantize(2.5 / 255.0), 3);
+        assert_eq!(Rounding::NearestEven.quantize(2.5 / 255.0), 2);
+        assert_eq!(Rounding::NearestEven.quantize(3.5 / 255.0), 4);
+
+        // just past a half, in linear
+        let input = encoded_to_linear(100) * 0.4 + encoded_to_linear(101) * 0.6;
+        assert_eq!(linear_to_encoded_with(input, Rounding::Truncate), 100);
+        assert_eq!(linear_to_encoded_with(input, Rounding::Nearest), 101);
+        assert_eq!(linear_to_encoded_with(input, Rounding::NearestEven), 101);
+
+        // equal bins differs from the others near the ends
+        assert_eq!(linear_to_encoded_with(0.999, Rounding::EqualBins), 255);
+        assert_eq!(linear_to_encoded_with(0.999, Rounding::Truncate), 254);
+        assert_eq!(
+            linear_to_encoded_with(encoded_to_linear(1) * 0.9, Rounding::EqualBins),
+            linear_to_encoded(encoded_to_linear(1) * 0.9)
+        );
+
+        // they all clamp the same way, and all but truncation give exact values back
+        for rounding in [
+            Rounding::EqualBins,
+            Rounding::Truncate,
+            Rounding::Nearest,
+            Rounding::NearestEven,
+        ] {
+            for c in 0..=255 {
+                let round_trip = linear_to_encoded_with(encoded_to_linear(c), rounding);
+
+                if rounding == Rounding::Truncate {
+                    assert!(c - round_trip <= 1);
+                } else {
+                    assert_eq!(round_trip, c, "{:?}", rounding);
+                }
+            }
+            assert_eq!(linear_to_encoded_with(-1.0, rounding), 0);
+            assert_eq!(linear_to_encoded_with(2.0, rounding), 255);
+            assert_eq!(linear_to_encoded_with(f32::NAN, rounding), 0);
+        }
+    }
+
+    #[test]
+    fn encoded_f32_to_linear() {
+        for c in 0..=255 {
+            let live = super::encoded_f32_to_linear(c as f32 / 255.0);
+            assert!((live - encoded_to_linear(c)).abs() < 0.000001, "{}: {}", c, live);
+        }
+
+        // and between the u8s, it's between their linear values
+        let between = super::encoded_f32_to_linear(100.5 / 255.0);
+        assert!(between > encoded_to_linear(100) && between < encoded_to_linear(101));
+    }
+
+    #[test]
+    fn high_precision() {
+        let values = [0.001, 0.003, 0.01, 0.18, 0.214, 0.5, 0.73, 0.999];
+
+        let (mut single_error, mut double_error) = (0.0, 0.0);
+        for linear in values {
+            single_error += (decode_f32(encode_f32(linear)) as f64 - linear as f64).abs();
+            double_error += (decode_f64(encode_f64(linear as f64)) - linear as f64).abs();
+        }
+        assert!(
+            double_error < single_error / 1000.0,
+            "{} {}",
+            single_error,
+            double_error
+        );
+
+        // and it matches the real sRGB curve, computed elsewhere
+        let references = [
+            (0.5, 0.21404114048223255),
+            (0.04, 0.0030959752321981426),
+            (0.7353569830524495, 0.5),
+            (0.49995554934020553, 0.214),
+            (1.0, 1.0),
+        ];
+        for (encoded, linear) in references {
+            assert!((decode_f64(encoded) - linear).abs() < 1e-15, "{}", encoded);
+            assert!((encode_f64(linear) - encoded).abs() < 1e-15, "{}", linear);
+        }
+
+        // the feature only changes what the public functions do inside
+        #[cfg(feature = "high-precision")]
+        for linear in values {
+            assert_eq!(linear_to_encoded_f32(linear), encode_f64(linear as f64) as f32);
+            assert_eq!(super::encoded_f32_to_linear(linear), decode_f64(linear as f64) as f32);
+        }
+    }
+
+    #[test]
+    fn to_encoded_checked() {
+        let color = LinearColor::new(0.0, 0.5, 1.0, 1.0);
+        assert_eq!(color.to_encoded_checked(), (color.to_encoded_space(), false));
+
+        let hdr = LinearColor::new(4.0, 0.5, 0.2, 1.0);
+        assert_eq!(hdr.to_encoded_checked(), (hdr.to_encoded_space(), true));
+        assert!(LinearColor::new(0.1, -0.01, 0.2, 1.0).to_encoded_checked().1);
+        assert!(LinearColor::new(0.1, 0.1, 0.2, 1.5).to_encoded_checked().1);
+        assert!(LinearColor::new(f32::NAN, 0.1, 0.2, 1.0).to_encoded_checked().1);
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut u = Unstructured::new(&[107, 158, 190, 255, 1, 2]);
+        assert_eq!(
+            EncodedColor::arbitrary(&mut u),
+            Ok(EncodedColor::new(107, 158, 190, 255))
+        );
+
+        let mut bytes = [0; 16];
+        bytes[..4].copy_from_slice(&1.0f32.to_le_bytes());
+        bytes[4..8].copy_from_slice(&0.5f32.to_le_bytes());
+        bytes[12..].copy_from_slice(&1.0f32.to_le_bytes());
+
+        let mut u = Unstructured::new(&bytes);
+        assert_eq!(LinearColor::arbitrary(&mut u), Ok(LinearColor::new(1.0, 0.5, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn from_nits() {
+        assert_eq!(
+            LinearColor::from_nits([203.0; 3], 1.0, 203.0),
+            LinearColor::new(1.0, 1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            LinearColor::from_nits([80.0, 40.0, 400.0], 0.5, 80.0),
+            LinearColor::new(1.0, 0.5, 5.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn nearest_encoded_exact() {
+        assert_eq!(
+            EncodedColor::WHITE.to_linear().nearest_encoded_exact(),
+            Some(EncodedColor::WHITE)
+        );
+
+        for i in 0..=255 {
+            let color = EncodedColor::new(i, 255 - i, i / 2, i);
+            assert_eq!(color.to_linear().nearest_encoded_exact(), Some(color));
+        }
+
+        let between = EncodedColor::new(100, 100, 100, 255)
+            .to_linear()
+            .lerp(EncodedColor::new(101, 100, 100, 255).to_linear(), 0.5);
+        assert_eq!(between.nearest_encoded_exact(), None);
+        assert_eq!(LinearColor::new(1.0, 1.0, 1.0, 0.5).nearest_encoded_exact(), None);
+    }
+
+    #[test]
+    fn channel_from_u32() {
+        const RGBA: [u8; 4] = [
+            EncodedColor::channel_from_rgba_u32(0x6b9ebe80, 0),
+            EncodedColor::channel_from_rgba_u32(0x6b9ebe80, 1),
+            EncodedColor::channel_from_rgba_u32(0x6b9ebe80, 2),
+            EncodedColor::channel_from_rgba_u32(0x6b9ebe80, 3),
+        ];
+        const BGRA: [u8; 4] = [
+            EncodedColor::channel_from_bgra_u32(0xbe9e6b80, 0),
+            EncodedColor::channel_from_bgra_u32(0xbe9e6b80, 1),
+            EncodedColor::channel_from_bgra_u32(0xbe9e6b80, 2),
+            EncodedColor::channel_from_bgra_u32(0xbe9e6b80, 3),
+        ];
+        const ARGB: [u8; 4] = [
+            EncodedColor::channel_from_argb_u32(0x806b9ebe, 0),
+            EncodedColor::channel_from_argb_u32(0x806b9ebe, 1),
+            EncodedColor::channel_from_argb_u32(0x806b9ebe, 2),
+            EncodedColor::channel_from_argb_u32(0x806b9ebe, 3),
+        ];
+
+        assert_eq!(RGBA, [107, 158, 190, 128]);
+        assert_eq!(BGRA, RGBA);
+        assert_eq!(ARGB, RGBA);
+
+        let color = EncodedColor::from_argb_u32(0x806b9ebe);
+        assert_eq!(EncodedColor::from(RGBA), color);
+        if cfg!(target_endian = "little") {
+            assert_eq!(EncodedColor::from_rgba_u32(0x6b9ebe80), color);
+            assert_eq!(EncodedColor::from_bgra_u32(0xbe9e6b80), color);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn channel_from_u32_index() {
+        let index = core::hint::black_box(4);
+        EncodedColor::channel_from_rgba_u32(0, index);
+    }
+
+    #[test]
+    fn requantize() {
+        let color = LinearColor::new(0.3, 0.123456, 0.9, 0.5);
+        let once = color.requantize();
+        assert_ne!(once, color);
+        assert_eq!(once.requantize(), once);
+        assert_eq!(once.to_encoded_space(), color.to_encoded_space().with_a(128));
+
+        for i in 0..=255 {
+            let color = EncodedColor::new(i, 255 - i, i / 3, i).to_linear();
+            assert_eq!(color.requantize(), color);
+        }
+
+        // out of range values are clamped onto the grid
+        let hdr = LinearColor::new(2.0, -1.0, 0.5, 1.5).requantize();
+        assert_eq!(hdr.requantize(), hdr);
+        assert_eq!((hdr.r, hdr.g, hdr.a), (1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn round_to() {
+        let color = LinearColor::new(0.123456, 0.5, 0.98765, 1.0);
+        assert_eq!(color.round_to(2), LinearColor::new(0.12, 0.5, 0.99, 1.0));
+        assert_eq!(color.round_to(0), LinearColor::new(0.0, 1.0, 1.0, 1.0));
+        assert_eq!(color.round_to(4), LinearColor::new(0.1235, 0.5, 0.9877, 1.0));
+        assert_eq!(color.round_to(9), color);
+
+        #[cfg(feature = "std")]
+        {
+            let rounded = std::format!("{}", color.round_to(3));
+            assert_eq!(rounded, "r: 0.123, g: 0.5, b: 0.988, a: 1");
+        }
+    }
+
+    #[test]
+    fn swizzle() {
+        let c = EncodedColor::new(1, 2, 3, 4);
+
+        assert_eq!(c.swizzle([0, 1, 2, 3]), c);
+        assert_eq!(c.swizzle([2, 1, 0, 3]), EncodedColor::new(3, 2, 1, 4));
+        assert_eq!(c.swizzle([0, 0, 0, 3]), EncodedColor::new(1, 1, 1, 4));
+        assert_eq!(c.to_bgra(), EncodedColor::new(3, 2, 1, 4));
+        assert_eq!(c.to_argb(), EncodedColor::new(4, 1, 2, 3));
+        assert_eq!(c.to_abgr(), EncodedColor::new(4, 3, 2, 1));
+        assert_eq!(c.to_bgra().to_bgra(), c);
+
+        const SWIZZLED: EncodedColor = EncodedColor::new(1, 2, 3, 4).swizzle([3, 3, 3, 3]);
+        assert_eq!(SWIZZLED, EncodedColor::new(4, 4, 4, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn swizzle_out_of_range() {
+        let _ = EncodedColor::WHITE.swizzle([0, 1, 2, 4]);
+    }
+
     #[test]
     fn from_u32s() {
         let cornwall_blue_in_rgba: u32 = 0x6b9ebeff;
@@ -686,6 +1871,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn argb_u32() {
+        let cornwall_blue_in_argb: u32 = 0xff6b9ebe;
+        let cornwall_encoded = EncodedColor::new(107, 158, 190, 255);
+
+        let encoded_argb = EncodedColor::from_argb_u32(cornwall_blue_in_argb);
+        assert_eq!(encoded_argb, cornwall_encoded);
+        assert_eq!(encoded_argb.to_argb_u32(), cornwall_blue_in_argb);
+        assert_eq!(EncodedColor::new(1, 2, 3, 4).to_argb_u32(), 0x04010203);
+    }
+
     #[test]
     fn encoding_decoding() {
         fn encode(input: u8, output: f32) {
@@ -737,6 +1933,80 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn transfer_constants() {
+        // rebuilding the decoding curve out of the constants gives us the LUT back...
+        for c in 0..=255u8 {
+            let e = c as f32 / 255.0;
+            let linear = if e > SRGB_ENCODED_THRESHOLD {
+                ((e + SRGB_ALPHA) / (1.0 + SRGB_ALPHA)).powf(SRGB_GAMMA)
+            } else {
+                e / SRGB_LINEAR_SLOPE
+            };
+            assert!((linear - encoded_to_linear(c)).abs() < 1e-6);
+        }
+
+        // ...and the encoding curve, the live path.
+        for i in 0..=1000 {
+            let linear = i as f32 / 1000.0;
+            let encoded = if linear >= SRGB_LINEAR_THRESHOLD {
+                (1.0 + SRGB_ALPHA) * linear.powf(1.0 / SRGB_GAMMA) - SRGB_ALPHA
+            } else {
+                SRGB_LINEAR_SLOPE * linear
+            };
+            assert_eq!((encoded * 256.0) as u8, linear_to_encoded_live(linear));
+        }
+
+        // the two thresholds are the same point on the curve
+        assert!((SRGB_LINEAR_THRESHOLD * SRGB_LINEAR_SLOPE - SRGB_ENCODED_THRESHOLD).abs() < 1e-5);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    #[cfg(feature = "std")]
+    fn test_decode_lut() {
+        // the inverse of the encoding transfer function, at 64 bits of precision,
+        // landing on the boundaries of the `* 256.0` in the live path.
+        fn threshold_high_precision(k: u32) -> f32 {
+            let e = k as f64 / 256.0;
+            (if e >= 0.04045 {
+                ((e + 0.055) / 1.055).powf(2.4)
+            } else {
+                e / 12.92
+            }) as f32
+        }
+
+        let expect = (1..=255u32).map(threshold_high_precision).collect::<std::vec::Vec<_>>();
+        assert_eq!(expect, LINEAR_TO_ENCODED_LUT);
+
+        // every threshold encodes to its own value, and the float right below it doesn't.
+        for (i, threshold) in LINEAR_TO_ENCODED_LUT.iter().enumerate() {
+            assert_eq!(linear_to_encoded_lut(*threshold), i as u8 + 1);
+            let below = f32::from_bits(threshold.to_bits() - 1);
+            assert_eq!(linear_to_encoded_lut(below), i as u8);
+        }
+
+        // out of range values clamp the same way the live path does
+        assert_eq!(linear_to_encoded_lut(-1.0), 0);
+        assert_eq!(linear_to_encoded_lut(f32::NAN), 0);
+        assert_eq!(linear_to_encoded_lut(2.0), 255);
+        assert_eq!(linear_to_encoded_lut(1.0), 255);
+
+        // and the two paths never disagree by more than a step.
+        let mut disagreements = 0;
+        for i in 0..=100_000u32 {
+            let input = i as f32 / 100_000.0;
+            let lut = linear_to_encoded_lut(input);
+            let live = linear_to_encoded_live(input);
+            assert!(lut.abs_diff(live) <= 1, "{} gave {} and {}", input, lut, live);
+            if lut != live {
+                disagreements += 1;
+            }
+        }
+        assert!(disagreements < 10);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn serde() {
@@ -0,0 +1,53 @@
+use crate::LinearColor;
+
+impl LinearColor {
+    /// Converts this color into LMS space, dropping alpha. LMS is the response of the long,
+    /// medium, and short wavelength cones in the eye, so it's the space to work in for anything
+    /// which models vision, like [simulate_color_blindness](Self::simulate_color_blindness).
+    ///
+    /// This uses the matrix from Viénot, Brettel, and Mollon's color blindness work, which most
+    /// simulations expect. It isn't normalized, so white is about `(65.5, 34.5, 1.7)`.
+    /// [Oklab](crate::Oklab) goes through an LMS-like space too, but its own, different one.
+    pub fn to_lms(self) -> (f32, f32, f32) {
+        (
+            17.8824 * self.r + 43.5161 * self.g + 4.11935 * self.b,
+            3.45565 * self.r + 27.1554 * self.g + 3.86714 * self.b,
+            0.0299566 * self.r + 0.184309 * self.g + 1.46709 * self.b,
+        )
+    }
+
+    /// Converts an LMS color, from [to_lms](Self::to_lms), back into linear space, with the
+    /// given alpha. LMS colors which don't come from real colors can give channels outside of
+    /// 0.0 to 1.0.
+    #[allow(clippy::excessive_precision)]
+    pub fn from_lms((l, m, s): (f32, f32, f32), a: f32) -> Self {
+        LinearColor {
+            r: 0.0809444479 * l - 0.130504409 * m + 0.116721066 * s,
+            g: -0.0102485335 * l + 0.0540193266 * m - 0.113614708 * s,
+            b: -0.000365296938 * l - 0.00412161469 * m + 0.693511405 * s,
+            a,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::close;
+
+    #[test]
+    fn lms_round_trip() {
+        for color in [
+            LinearColor::new(0.0, 0.0, 0.0, 1.0),
+            LinearColor::new(1.0, 1.0, 1.0, 0.5),
+            LinearColor::new(1.0, 0.0, 0.0, 1.0),
+            LinearColor::new(0.0, 1.0, 0.0, 1.0),
+            LinearColor::new(0.0, 0.0, 1.0, 1.0),
+            LinearColor::new(0.147, 0.342, 0.515, 0.25),
+        ] {
+            let round_trip = LinearColor::from_lms(color.to_lms(), color.a);
+
+            assert!(close(round_trip, color), "{:?}", round_trip);
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{ENCODED_TO_LINEAR_LUT, EncodedColor, LINEAR_TO_ENCODED_LUT, LinearColor, math};
+
+/// [ENCODED_TO_LINEAR_LUT], as 16 bit fixed point, for [EncodedColor::grayscale_fast].
+const ENCODED_TO_LINEAR_U16: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (ENCODED_TO_LINEAR_LUT[i] * 65535.0 + 0.5) as u16;
+        i += 1;
+    }
+
+    table
+};
+
+/// Encodes 12 bit linear values, for [EncodedColor::grayscale_fast]. Entry `k` covers the 16 bit
+/// linear values from `k * 16` to `k * 16 + 15`, and is the encoding of the middle of those.
+const LINEAR_U12_TO_ENCODED: [u8; 4096] = {
+    let mut table = [0; 4096];
+    let mut encoded = 0;
+    let mut k = 0;
+    while k < 4096 {
+        let linear = (k * 16 + 8) as f32 / 65535.0;
+
+        // the thresholds only go up, so we can pick up where the last entry left off
+        while encoded < 255 && LINEAR_TO_ENCODED_LUT[encoded] <= linear {
+            encoded += 1;
+        }
+
+        table[k] = encoded as u8;
+        k += 1;
+    }
+
+    table
+};
+
+impl LinearColor {
+    /// The relative luminance of this color, from 0.0 (black) to 1.0 (white), ignoring alpha.
+    ///
+    /// This is the physically-based brightness, using the Rec. 709 (and sRGB) weights
+    /// `0.2126 r + 0.7152 g + 0.0722 b`. It's what WCAG contrast and most rendering math use.
+    pub fn luminance(self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Brings this color into gamut by mixing in just enough of the gray `(luminance,
+    /// luminance, luminance)`, where `luminance` is this color's, from 0.0 to 1.0. Luminance is
+    /// linear, so every color along the way is just as bright. Alpha is kept.
+    pub(crate) fn fit_toward_gray(self, luminance: f32) -> LinearColor {
+        // how far from the gray toward this color we can go and stay in gamut
+        let mut t: f32 = 1.0;
+        for c in [self.r, self.g, self.b] {
+            if c > 1.0 {
+                t = t.min((1.0 - luminance) / (c - luminance));
+            } else if c < 0.0 {
+                t = t.min(luminance / (luminance - c));
+            }
+        }
+        let channel = |c: f32| luminance + (c - luminance) * t;
+
+        LinearColor::new(channel(self.r), channel(self.g), channel(self.b), self.a)
+    }
+
+    /// Keeps this color if its [luminance](Self::luminance) is above `threshold`, and gives
+    /// transparent black otherwise. This is the bright-pass at the start of a bloom effect.
+    ///
+    /// The color is kept as-is, not reduced by the threshold. If you want a softer knee,
+    /// subtract from the result yourself.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn threshold_luminance(self, threshold: f32) -> LinearColor {
+        if self.luminance() > threshold {
+            self
+        } else {
+            LinearColor::new(0.0, 0.0, 0.0, 0.0)
+        }
+    }
+}
+
+impl EncodedColor {
+    /// The relative luminance of this color, from 0.0 (black) to 1.0 (white), ignoring alpha.
+    /// See [LinearColor::luminance].
+    pub fn luminance(self) -> f32 {
+        self.to_linear().luminance()
+    }
+
+    /// Makes this color exactly as bright as `target`, a [luminance](Self::luminance) from 0.0
+    /// to 1.0 (clamped), for making a set of swatches equally bright. Alpha is kept.
+    ///
+    /// We scale the color in linear space, which keeps its hue and saturation. When that would
+    /// push a channel past 1.0, like making a blue very bright, we mix in just enough gray of
+    /// the same luminance to fit, so the result is the closest color in sRGB with that
+    /// brightness. Black has no hue to keep, so it becomes a gray.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn with_luminance(self, target: f32) -> EncodedColor {
+        let target = target.clamp(0.0, 1.0);
+        let linear = self.to_linear();
+        let luminance = linear.luminance();
+        if luminance <= 0.0 {
+            let gray = crate::linear_to_encoded(target);
+            return EncodedColor::new(gray, gray, gray, self.a);
+        }
+
+        let scale = target / luminance;
+        LinearColor::new(linear.r * scale, linear.g * scale, linear.b * scale, 1.0)
+            .fit_toward_gray(target)
+            .to_encoded_space()
+            .with_a(self.a)
+    }
+
+    /// Converts this color to the gray with the same [luminance](Self::luminance), which is the
+    /// gamma-correct way to desaturate. Alpha is kept.
+    ///
+    /// For whole images, [grayscale_fast](Self::grayscale_fast) gives nearly the same results
+    /// much faster.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn grayscale(self) -> EncodedColor {
+        let gray = crate::linear_to_encoded(self.luminance());
+
+        EncodedColor::new(gray, gray, gray, self.a)
+    }
+
+    /// Fades this color toward its [grayscale](Self::grayscale) version, where a `t` of 0.0
+    /// gives this color and 1.0 gives the gray. `t` is clamped to that range. Alpha is kept.
+    ///
+    /// The fade happens in linear space, so the color stays just as bright the whole way,
+    /// which is what you want for animating a disabled control to gray.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn mix_with_grayscale(self, t: f32) -> EncodedColor {
+        let t = t.clamp(0.0, 1.0);
+        let linear = self.to_linear();
+        let gray = linear.luminance();
+        let channel = |c: f32| c * (1.0 - t) + gray * t;
+
+        EncodedColor::new(
+            crate::linear_to_encoded(channel(linear.r)),
+            crate::linear_to_encoded(channel(linear.g)),
+            crate::linear_to_encoded(channel(linear.b)),
+            self.a,
+        )
+    }
+
+    /// Like [grayscale](Self::grayscale), but with integer math and lookup tables, rather than
+    /// floats and the transfer function. This is meant for converting whole images.
+    ///
+    /// The luminance is computed in 16 bit fixed point and looked up at 12 bits, so this is
+    /// within one step of [grayscale](Self::grayscale), and the same for most colors.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn grayscale_fast(self) -> EncodedColor {
+        // the Rec. 709 weights, out of 65536
+        let luminance = 13933 * ENCODED_TO_LINEAR_U16[self.r as usize] as u32
+            + 46871 * ENCODED_TO_LINEAR_U16[self.g as usize] as u32
+            + 4732 * ENCODED_TO_LINEAR_U16[self.b as usize] as u32;
+        let gray = LINEAR_U12_TO_ENCODED[(luminance >> 20) as usize];
+
+        EncodedColor::new(gray, gray, gray, self.a)
+    }
+
+    /// The WCAG 2 contrast ratio between this color and `other`, from 1.0 (the same) to 21.0
+    /// (black and white). The order doesn't matter, and alpha is ignored.
+    pub fn contrast_ratio(self, other: EncodedColor) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Whether text in this color on `other` (or the other way around) is readable enough for
+    /// WCAG level AA, which needs a [contrast_ratio](Self::contrast_ratio) of at least 4.5 for
+    /// regular text.
+    pub fn passes_wcag_aa(self, other: EncodedColor) -> bool {
+        self.contrast_ratio(other) >= 4.5
+    }
+
+    /// Whether this is a dark color, which wants light text or overlays on top of it. Alpha is
+    /// ignored.
+    ///
+    /// A color is dark when its [luminance](Self::luminance) is below 0.179, where white and
+    /// black text have the same [contrast_ratio](Self::contrast_ratio) on it. For grays, that's
+    /// between 117 (dark) and 118 (light). To use your own cutoff, see
+    /// [is_dark_with](Self::is_dark_with).
+    pub fn is_dark(self) -> bool {
+        self.is_dark_with(0.179)
+    }
+
+    /// Whether this is a light color, which wants dark text or overlays on top of it. This is
+    /// always the opposite of [is_dark](Self::is_dark).
+    pub fn is_light(self) -> bool {
+        !self.is_dark()
+    }
+
+    /// Whether this color's [luminance](Self::luminance) is below `threshold`, which goes from
+    /// 0.0 (nothing is dark) to 1.0 (everything but white is dark). Alpha is ignored.
+    pub fn is_dark_with(self, threshold: f32) -> bool {
+        self.luminance() < threshold
+    }
+
+    /// The perceived brightness of this color, from 0.0 to 1.0, ignoring alpha, using the
+    /// [HSP](https://alienryderflex.com/hsp.html) model: `sqrt(0.299 r² + 0.587 g² + 0.114 b²)`
+    /// on the encoded channels.
+    ///
+    /// This isn't physically based like [luminance](Self::luminance), but some people find it
+    /// matches their eyes better for quick UI heuristics, like picking a text color. Which one
+    /// you use is up to you.
+    pub fn perceived_brightness(self) -> f32 {
+        let [r, g, b, _] = self.to_encoded_f32s();
+
+        math::sqrt(0.299 * r * r + 0.587 * g * g + 0.114 * b * b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luminance() {
+        assert!((EncodedColor::WHITE.luminance() - 1.0).abs() < 0.0001);
+        assert_eq!(EncodedColor::BLACK.luminance(), 0.0);
+        assert!((EncodedColor::RED.luminance() - 0.2126).abs() < 0.0001);
+        assert!((EncodedColor::GREEN.luminance() - 0.7152).abs() < 0.0001);
+        assert!((EncodedColor::new(128, 128, 128, 0).luminance() - 0.2158605).abs() < 0.0001);
+    }
+
+    #[test]
+    fn perceived_brightness() {
+        let check = |c: EncodedColor, expected: f32| {
+            assert!((c.perceived_brightness() - expected).abs() < 0.0001, "{:?}", c);
+        };
+
+        check(EncodedColor::WHITE, 1.0);
+        check(EncodedColor::BLACK, 0.0);
+        check(EncodedColor::RED, 0.546809);
+        check(EncodedColor::GREEN, 0.766159);
+        check(EncodedColor::BLUE, 0.337639);
+        check(EncodedColor::new(107, 158, 190, 255), 0.584202);
+
+        // for grays, it is just the encoded value
+        check(EncodedColor::new(51, 51, 51, 255), 0.2);
+    }
+
+    #[test]
+    fn threshold_luminance() {
+        let clear = LinearColor::new(0.0, 0.0, 0.0, 0.0);
+
+        // HDR colors are what usually make it through
+        let bright = LinearColor::new(4.0, 3.0, 2.0, 1.0);
+        assert_eq!(bright.threshold_luminance(1.0), bright);
+
+        let dim = LinearColor::new(0.2, 0.1, 0.3, 1.0);
+        assert_eq!(dim.threshold_luminance(1.0), clear);
+        assert_eq!(dim.threshold_luminance(0.1), dim);
+
+        // right on the threshold doesn't count
+        let white = LinearColor::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(white.threshold_luminance(white.luminance()), clear);
+    }
+
+    #[test]
+    fn is_dark() {
+        assert!(EncodedColor::BLACK.is_dark());
+        assert!(EncodedColor::WHITE.is_light());
+        assert!(EncodedColor::BLUE.is_dark());
+        // black text is easier to read on pure red than white text is
+        assert!(EncodedColor::RED.is_light());
+        assert!(EncodedColor::GREEN.is_light());
+        assert!(EncodedColor::YELLOW.is_light());
+
+        // right around the threshold
+        assert!(EncodedColor::new(117, 117, 117, 255).is_dark());
+        assert!(EncodedColor::new(118, 118, 118, 255).is_light());
+
+        // and at the threshold, white and black text are about as readable as each other
+        let gray = EncodedColor::new(118, 118, 118, 255);
+        let (on_white, on_black) = (
+            gray.contrast_ratio(EncodedColor::WHITE),
+            gray.contrast_ratio(EncodedColor::BLACK),
+        );
+        assert!((on_white - on_black).abs() < 0.1, "{} {}", on_white, on_black);
+
+        // alpha doesn't matter
+        assert!(EncodedColor::BLACK.with_a(0).is_dark());
+
+        let sky = EncodedColor::new(107, 158, 190, 255);
+        assert!(sky.is_light());
+        assert!(sky.is_dark_with(0.5));
+        assert!(!sky.is_dark_with(sky.luminance()));
+        assert!(!EncodedColor::BLACK.is_dark_with(0.0));
+        assert!(!EncodedColor::WHITE.is_dark_with(1.0));
+    }
+
+    #[test]
+    fn with_luminance() {
+        let colors = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(200, 30, 30, 100),
+            EncodedColor::BLUE,
+            EncodedColor::YELLOW,
+            EncodedColor::WHITE,
+            EncodedColor::BLACK,
+        ];
+
+        for color in colors {
+            for target in [0.0, 0.05, 0.2, 0.5, 0.9, 1.0] {
+                let output = color.with_luminance(target);
+                assert!(
+                    (output.luminance() - target).abs() < 0.01,
+                    "{:?} {} {:?} {}",
+                    color,
+                    target,
+                    output,
+                    output.luminance()
+                );
+                assert_eq!(output.a, color.a);
+            }
+        }
+
+        // when it fits, the hue stays the same
+        let sky = EncodedColor::new(107, 158, 190, 255);
+        let dim = sky.with_luminance(0.1);
+        assert!((dim.to_oklch().h - sky.to_oklch().h).abs() < 2.0);
+        assert!(dim.to_oklch().l < sky.to_oklch().l);
+
+        // and a very bright blue is as blue as it can be, rather than clipped to white
+        let bright_blue = EncodedColor::BLUE.with_luminance(0.5);
+        assert!(bright_blue.b == 255 && bright_blue.r < 255);
+
+        assert_eq!(sky.with_luminance(-1.0), EncodedColor::BLACK);
+        assert_eq!(sky.with_luminance(2.0), EncodedColor::WHITE);
+    }
+
+    #[test]
+    fn mix_with_grayscale() {
+        let colors = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(200, 30, 30, 100),
+            EncodedColor::YELLOW,
+            EncodedColor::BLACK,
+        ];
+
+        for color in colors {
+            assert_eq!(color.mix_with_grayscale(0.0), color);
+            assert_eq!(color.mix_with_grayscale(1.0), color.grayscale());
+            assert_eq!(color.mix_with_grayscale(-1.0), color);
+            assert_eq!(color.mix_with_grayscale(2.0), color.grayscale());
+
+            // it stays as bright on the way
+            let halfway = color.mix_with_grayscale(0.5);
+            assert!((halfway.luminance() - color.luminance()).abs() < 0.01);
+            assert_eq!(halfway.a, color.a);
+        }
+
+        let halfway = EncodedColor::new(200, 30, 30, 255).mix_with_grayscale(0.5);
+        assert!(halfway.to_oklch().c < EncodedColor::new(200, 30, 30, 255).to_oklch().c);
+        assert!(halfway.to_oklch().c > 0.05);
+    }
+
+    #[test]
+    fn contrast_ratio() {
+        let (black, white) = (EncodedColor::BLACK, EncodedColor::WHITE);
+        assert!((black.contrast_ratio(white) - 21.0).abs() < 0.001);
+        assert_eq!(black.contrast_ratio(white), white.contrast_ratio(black));
+        assert_eq!(white.contrast_ratio(white), 1.0);
+
+        // #777 on white is the classic just-fails-AA gray
+        let gray = EncodedColor::new(0x77, 0x77, 0x77, 255);
+        assert!((gray.contrast_ratio(white) - 4.48).abs() < 0.01);
+        assert!(!gray.passes_wcag_aa(white));
+        assert!(EncodedColor::new(0x76, 0x76, 0x76, 255).passes_wcag_aa(white));
+        assert!(black.passes_wcag_aa(white));
+    }
+
+    #[test]
+    fn grayscale() {
+        assert_eq!(EncodedColor::WHITE.grayscale(), EncodedColor::WHITE);
+        assert_eq!(EncodedColor::BLACK.with_a(3).grayscale(), EncodedColor::BLACK.with_a(3));
+        assert_eq!(
+            EncodedColor::new(77, 77, 77, 255).grayscale(),
+            EncodedColor::new(77, 77, 77, 255)
+        );
+
+        let mut off_by_one = 0;
+        for r in (0..=255).step_by(5) {
+            for g in (0..=255).step_by(5) {
+                for b in (0..=255).step_by(5) {
+                    let color = EncodedColor::new(r, g, b, 200);
+                    let (exact, fast) = (color.grayscale(), color.grayscale_fast());
+
+                    assert!(exact.r.abs_diff(fast.r) <= 1, "{:?}: {:?} vs {:?}", color, exact, fast);
+                    assert_eq!((fast.r, fast.a), (fast.g, 200));
+                    assert_eq!(fast.g, fast.b);
+                    if exact != fast {
+                        off_by_one += 1;
+                    }
+                }
+            }
+        }
+
+        // and it's almost always exact
+        assert!(off_by_one < 52 * 52 * 52 / 50, "{}", off_by_one);
+
+        // grays stay put
+        for v in 0..=255 {
+            let gray = EncodedColor::new(v, v, v, 255);
+            assert!(gray.grayscale_fast().r.abs_diff(v) <= 1);
+        }
+    }
+}
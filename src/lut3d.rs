@@ -0,0 +1,318 @@
+use crate::{EncodedColor, LinearColor};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A 3D color lookup table, like the ones used for film-style color grading.
+///
+/// These are normally loaded from `.cube` files with [from_cube](Self::from_cube). Applying one
+/// looks up each color in the table, with trilinear interpolation between the entries.
+///
+/// A LUT is only correct in the color space it was made for, and most LUTs from grading tools
+/// expect *encoded* sRGB (or a camera log space), not linear. [apply](Self::apply) uses whatever
+/// values you give it, so for an sRGB LUT, use [apply_encoded](Self::apply_encoded) instead,
+/// which passes the encoded values through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+
+    // red changes fastest, then green, then blue, like in `.cube` files.
+    data: Vec<[f32; 3]>,
+}
+
+/// The error returned when [Lut3d::from_cube] can't parse a `.cube` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseCubeError {
+    /// The file never gave a `LUT_3D_SIZE`.
+    MissingSize,
+
+    /// The file is a 1D LUT (it has a `LUT_1D_SIZE`), which [Lut3d] can't hold.
+    Unsupported1d,
+
+    /// The line, counting from 1, couldn't be understood.
+    InvalidLine(usize),
+
+    /// The file had the wrong number of entries for its size.
+    WrongEntryCount {
+        /// The number of entries the size called for.
+        expected: usize,
+
+        /// The number of entries the file had.
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseCubeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCubeError::MissingSize => f.write_str("cube file has no `LUT_3D_SIZE`"),
+            ParseCubeError::Unsupported1d => f.write_str("1D cube files aren't supported"),
+            ParseCubeError::InvalidLine(line) => write!(f, "invalid cube file on line {}", line),
+            ParseCubeError::WrongEntryCount { expected, found } => {
+                write!(f, "cube file should have {} entries, but has {}", expected, found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCubeError {}
+
+impl Lut3d {
+    /// Creates a LUT which maps every color to itself, with `size` entries along each side.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2.
+    pub fn identity(size: usize) -> Self {
+        assert!(size >= 2, "a 3D LUT needs at least 2 entries per side");
+
+        let step = |i: usize| i as f32 / (size - 1) as f32;
+        let mut data = Vec::with_capacity(size * size * size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    data.push([step(r), step(g), step(b)]);
+                }
+            }
+        }
+
+        Self {
+            size,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            data,
+        }
+    }
+
+    /// Parses the contents of a `.cube` file (the Adobe/Resolve format).
+    ///
+    /// `TITLE`, `DOMAIN_MIN`, `DOMAIN_MAX`, `LUT_3D_SIZE`, comments, and blank lines are
+    /// understood. 1D LUTs aren't supported.
+    pub fn from_cube(text: &str) -> Result<Self, ParseCubeError> {
+        fn floats(words: core::str::SplitWhitespace<'_>, line: usize) -> Result<[f32; 3], ParseCubeError> {
+            let mut output = [0.0; 3];
+            let mut count = 0;
+
+            for word in words {
+                let slot = output.get_mut(count).ok_or(ParseCubeError::InvalidLine(line))?;
+                *slot = word.parse().map_err(|_| ParseCubeError::InvalidLine(line))?;
+                count += 1;
+            }
+
+            if count == 3 {
+                Ok(output)
+            } else {
+                Err(ParseCubeError::InvalidLine(line))
+            }
+        }
+
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut data = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let keyword = words.clone().next().unwrap_or_default();
+
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => return Err(ParseCubeError::Unsupported1d),
+                "LUT_3D_SIZE" => {
+                    words.next();
+                    let parsed = words
+                        .next()
+                        .and_then(|w| w.parse::<usize>().ok())
+                        // anything whose cube doesn't fit in a usize couldn't be in memory anyway
+                        .filter(|s| *s >= 2 && s.checked_pow(3).is_some())
+                        .ok_or(ParseCubeError::InvalidLine(line_number))?;
+                    size = Some(parsed);
+                }
+                "DOMAIN_MIN" => {
+                    words.next();
+                    domain_min = floats(words, line_number)?;
+                }
+                "DOMAIN_MAX" => {
+                    words.next();
+                    domain_max = floats(words, line_number)?;
+                }
+                _ => data.push(floats(words, line_number)?),
+            }
+        }
+
+        let size = size.ok_or(ParseCubeError::MissingSize)?;
+        let expected = size * size * size;
+        if data.len() != expected {
+            return Err(ParseCubeError::WrongEntryCount {
+                expected,
+                found: data.len(),
+            });
+        }
+
+        Ok(Self {
+            size,
+            domain_min,
+            domain_max,
+            data,
+        })
+    }
+
+    /// The number of entries along each side of the LUT.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Looks up a color in this LUT, interpolating between the nearest entries. Inputs outside
+    /// of the LUT's domain (normally 0.0 to 1.0) are clamped to it. Alpha is passed through.
+    ///
+    /// This uses the channels as they are, so make sure the color is in the space the LUT
+    /// expects. See the [type docs](Self).
+    pub fn apply(&self, color: LinearColor) -> LinearColor {
+        let max_index = (self.size - 1) as f32;
+        let position = |c: f32, axis: usize| {
+            let t = (c - self.domain_min[axis]) / (self.domain_max[axis] - self.domain_min[axis]);
+
+            // NaN fails the clamp, so we send it to 0.
+            let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+            t * max_index
+        };
+
+        let r = position(color.r, 0);
+        let g = position(color.g, 1);
+        let b = position(color.b, 2);
+
+        // the lower corner of the cell we're in, and how far into it we are
+        let r0 = (r as usize).min(self.size - 2);
+        let g0 = (g as usize).min(self.size - 2);
+        let b0 = (b as usize).min(self.size - 2);
+        let (fr, fg, fb) = (r - r0 as f32, g - g0 as f32, b - b0 as f32);
+
+        let at = |r: usize, g: usize, b: usize| self.data[r + self.size * (g + self.size * b)];
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| {
+            [
+                a[0] + (b[0] - a[0]) * t,
+                a[1] + (b[1] - a[1]) * t,
+                a[2] + (b[2] - a[2]) * t,
+            ]
+        };
+
+        let c00 = lerp(at(r0, g0, b0), at(r0 + 1, g0, b0), fr);
+        let c10 = lerp(at(r0, g0 + 1, b0), at(r0 + 1, g0 + 1, b0), fr);
+        let c01 = lerp(at(r0, g0, b0 + 1), at(r0 + 1, g0, b0 + 1), fr);
+        let c11 = lerp(at(r0, g0 + 1, b0 + 1), at(r0 + 1, g0 + 1, b0 + 1), fr);
+
+        let c0 = lerp(c00, c10, fg);
+        let c1 = lerp(c01, c11, fg);
+        let [r, g, b] = lerp(c0, c1, fb);
+
+        LinearColor::new(r, g, b, color.a)
+    }
+
+    /// Looks up an encoded color in this LUT, for LUTs which expect encoded sRGB (which is most
+    /// of them). The encoded values go straight in, without being linearized, and the results
+    /// come straight back out. Alpha is passed through.
+    pub fn apply_encoded(&self, color: EncodedColor) -> EncodedColor {
+        let [r, g, b, _] = color.to_encoded_f32s();
+        let output = self.apply(LinearColor::new(r, g, b, 1.0));
+        let round = |c: f32| (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+
+        EncodedColor::new(round(output.r), round(output.g), round(output.b), color.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::close;
+
+    const IDENTITY_CUBE: &str = "# an identity LUT
+TITLE \"identity\"
+LUT_3D_SIZE 2
+DOMAIN_MIN 0.0 0.0 0.0
+DOMAIN_MAX 1.0 1.0 1.0
+
+0.0 0.0 0.0
+1.0 0.0 0.0
+0.0 1.0 0.0
+1.0 1.0 0.0
+0.0 0.0 1.0
+1.0 0.0 1.0
+0.0 1.0 1.0
+1.0 1.0 1.0
+";
+
+    #[test]
+    fn identity() {
+        let lut = Lut3d::from_cube(IDENTITY_CUBE).unwrap();
+        assert_eq!(lut.size(), 2);
+        assert_eq!(lut, Lut3d::identity(2));
+
+        for lut in [lut, Lut3d::identity(17)] {
+            for color in [
+                LinearColor::new(0.0, 0.0, 0.0, 1.0),
+                LinearColor::new(1.0, 1.0, 1.0, 0.5),
+                LinearColor::new(0.25, 0.5, 0.75, 0.0),
+                LinearColor::new(0.123, 0.987, 0.456, 1.0),
+            ] {
+                assert!(close(lut.apply(color), color), "{:?}", lut.apply(color));
+            }
+
+            let color = EncodedColor::new(107, 158, 190, 20);
+            assert_eq!(lut.apply_encoded(color), color);
+
+            // out of the domain is clamped
+            assert!(close(
+                lut.apply(LinearColor::new(2.0, -1.0, 0.5, 1.0)),
+                LinearColor::new(1.0, 0.0, 0.5, 1.0)
+            ));
+        }
+    }
+
+    #[test]
+    fn interpolates() {
+        // swaps red and blue
+        let cube = "LUT_3D_SIZE 2
+0 0 0
+0 0 1
+0 1 0
+0 1 1
+1 0 0
+1 0 1
+1 1 0
+1 1 1";
+        let lut = Lut3d::from_cube(cube).unwrap();
+        let out = lut.apply(LinearColor::new(0.2, 0.4, 0.9, 1.0));
+        assert!(close(out, LinearColor::new(0.9, 0.4, 0.2, 1.0)), "{:?}", out);
+    }
+
+    #[test]
+    fn bad_cubes() {
+        assert_eq!(Lut3d::from_cube("0 0 0"), Err(ParseCubeError::MissingSize));
+        assert_eq!(Lut3d::from_cube("LUT_1D_SIZE 2"), Err(ParseCubeError::Unsupported1d));
+        assert_eq!(Lut3d::from_cube("LUT_3D_SIZE 1"), Err(ParseCubeError::InvalidLine(1)));
+        assert_eq!(
+            Lut3d::from_cube("LUT_3D_SIZE 4294967296"),
+            Err(ParseCubeError::InvalidLine(1))
+        );
+        assert_eq!(
+            Lut3d::from_cube("LUT_3D_SIZE 2\n\n0 0 zero"),
+            Err(ParseCubeError::InvalidLine(3))
+        );
+        assert_eq!(
+            Lut3d::from_cube("LUT_3D_SIZE 2\n0 0 0 0"),
+            Err(ParseCubeError::InvalidLine(2))
+        );
+        assert_eq!(
+            Lut3d::from_cube("LUT_3D_SIZE 2\n0 0 0"),
+            Err(ParseCubeError::WrongEntryCount { expected: 8, found: 1 })
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::EncodedColor;
+
+/// Creates an [EncodedColor] from a hex literal like `0x6b9ebe` at compile time, with an alpha
+/// of 255. This works in consts, and never parses anything at runtime.
+///
+/// ```
+/// use smol_rgb::{EncodedColor, rgb};
+///
+/// const ACCENT: EncodedColor = rgb!(0x6b9ebe);
+/// assert_eq!(ACCENT, EncodedColor::new(107, 158, 190, 255));
+/// ```
+///
+/// The literal has to be `0x` and then exactly six hex digits (underscores are fine), so
+/// typos are compile errors:
+///
+/// ```compile_fail
+/// let oops = smol_rgb::rgb!(0x6b9eb);
+/// ```
+///
+/// For an alpha channel, use [rgba!](crate::rgba!).
+#[macro_export]
+macro_rules! rgb {
+    ($hex:literal) => {{
+        const COLOR: $crate::EncodedColor = $crate::__parse_hex_literal(stringify!($hex), false);
+        COLOR
+    }};
+}
+
+/// Creates an [EncodedColor] from a hex literal like `0x6b9ebe80` at compile time, in
+/// `0xRRGGBBAA` order. This works in consts, and never parses anything at runtime.
+///
+/// ```
+/// use smol_rgb::{EncodedColor, rgba};
+///
+/// const SHADOW: EncodedColor = rgba!(0x00000080);
+/// assert_eq!(SHADOW, EncodedColor::new(0, 0, 0, 128));
+/// ```
+///
+/// The literal has to be `0x` and then exactly eight hex digits (underscores are fine), so
+/// typos are compile errors:
+///
+/// ```compile_fail
+/// let oops = smol_rgb::rgba!(0x6b9ebe);
+/// ```
+///
+/// For an opaque color, use [rgb!](crate::rgb!).
+#[macro_export]
+macro_rules! rgba {
+    ($hex:literal) => {{
+        const COLOR: $crate::EncodedColor = $crate::__parse_hex_literal(stringify!($hex), true);
+        COLOR
+    }};
+}
+
+/// Parses the text of the literal given to [rgb!](crate::rgb!) or [rgba!](crate::rgba!). This
+/// only runs at compile time, so the panics here are how those macros give compile errors.
+#[doc(hidden)]
+pub const fn parse_hex_literal(literal: &str, has_alpha: bool) -> EncodedColor {
+    let bytes = literal.as_bytes();
+    if bytes.len() < 2 || bytes[0] != b'0' || (bytes[1] != b'x' && bytes[1] != b'X') {
+        panic!("color literals must start with `0x`");
+    }
+
+    let mut value: u32 = 0;
+    let mut digits = 0;
+    let mut i = 2;
+    while i < bytes.len() {
+        let digit = match bytes[i] {
+            b'0'..=b'9' => bytes[i] - b'0',
+            b'a'..=b'f' => bytes[i] - b'a' + 10,
+            b'A'..=b'F' => bytes[i] - b'A' + 10,
+            b'_' => {
+                i += 1;
+                continue;
+            }
+            _ => panic!("color literals can only have hex digits, and no suffix"),
+        };
+
+        // we check the count below, so this only has to not overflow
+        if digits < 8 {
+            value = value << 4 | digit as u32;
+        }
+        digits += 1;
+        i += 1;
+    }
+
+    if has_alpha {
+        if digits != 8 {
+            panic!("`rgba!` needs exactly 8 hex digits, like `0x6b9ebeff`");
+        }
+        EncodedColor::from_rgba_u32(value)
+    } else {
+        if digits != 6 {
+            panic!("`rgb!` needs exactly 6 hex digits, like `0x6b9ebe`");
+        }
+        EncodedColor::from_rgba_u32(value << 8 | 0xff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKY: EncodedColor = rgb!(0x6b9ebe);
+    const SKY_CLEAR: EncodedColor = rgba!(0x6B9E_BE00);
+
+    #[test]
+    fn macros() {
+        assert_eq!(SKY, EncodedColor::new(107, 158, 190, 255));
+        assert_eq!(SKY_CLEAR, SKY.with_a(0));
+        assert_eq!(rgb!(0xFFFFFF), EncodedColor::WHITE);
+        assert_eq!(rgba!(0x00000000), EncodedColor::CLEAR);
+        assert_eq!(rgb!(0x00_ff_00), EncodedColor::GREEN);
+    }
+}
@@ -0,0 +1,55 @@
+//! The float functions we need which aren't in `core`. With `std`, these are just the inherent
+//! methods on `f32`. Without it, we use `libm`. If both are on, `std` wins.
+
+macro_rules! float_fns {
+    ($($name:ident => $libm:ident),* $(,)?) => {
+        $(
+            #[cfg(feature = "std")]
+            #[inline]
+            pub(crate) fn $name(f: f32) -> f32 {
+                f.$name()
+            }
+
+            #[cfg(all(not(feature = "std"), feature = "libm"))]
+            #[inline]
+            pub(crate) fn $name(f: f32) -> f32 {
+                libm::$libm(f)
+            }
+        )*
+    };
+}
+
+macro_rules! float_fns2 {
+    ($($name:ident => $libm:ident),* $(,)?) => {
+        $(
+            #[cfg(feature = "std")]
+            #[inline]
+            pub(crate) fn $name(f: f32, other: f32) -> f32 {
+                f.$name(other)
+            }
+
+            #[cfg(all(not(feature = "std"), feature = "libm"))]
+            #[inline]
+            pub(crate) fn $name(f: f32, other: f32) -> f32 {
+                libm::$libm(f, other)
+            }
+        )*
+    };
+}
+
+float_fns!(cbrt => cbrtf, cos => cosf, round => roundf, sin => sinf, sqrt => sqrtf);
+float_fns2!(atan2 => atan2f, powf => powf);
+
+// the transfer functions are the only place we need f64, with the `high-precision` feature.
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn powf64(f: f64, other: f64) -> f64 {
+    f.powf(other)
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+#[inline]
+pub(crate) fn powf64(f: f64, other: f64) -> f64 {
+    libm::pow(f, other)
+}
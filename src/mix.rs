@@ -0,0 +1,562 @@
+use crate::{EncodedColor, LinearColor};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Neg};
+
+impl LinearColor {
+    /// Linearly interpolates between this color and `other`, per channel (including alpha).
+    /// A `t` of 0.0 gives `self` and a `t` of 1.0 gives `other`.
+    ///
+    /// `t` isn't clamped, so values outside of 0.0 to 1.0 extrapolate.
+    #[inline]
+    pub fn lerp(self, other: LinearColor, t: f32) -> LinearColor {
+        LinearColor {
+            r: self.r + (other.r - self.r) * t,
+            g: self.g + (other.g - self.g) * t,
+            b: self.b + (other.b - self.b) * t,
+            a: self.a + (other.a - self.a) * t,
+        }
+    }
+
+    /// Blends three colors with barycentric weights, like the vertex colors of a triangle in a
+    /// software rasterizer. This is the weighted sum of the colors, including alpha, so the
+    /// weights should add up to about 1.0.
+    #[inline]
+    pub fn barycentric(colors: [LinearColor; 3], weights: [f32; 3]) -> LinearColor {
+        let [c0, c1, c2] = colors;
+        let [w0, w1, w2] = weights;
+
+        LinearColor {
+            r: c0.r * w0 + c1.r * w1 + c2.r * w2,
+            g: c0.g * w0 + c1.g * w1 + c2.g * w2,
+            b: c0.b * w0 + c1.b * w1 + c2.b * w2,
+            a: c0.a * w0 + c1.a * w1 + c2.a * w2,
+        }
+    }
+
+    /// Interpolates between this color and `other` like [lerp](Self::lerp), but eases in and
+    /// out with the smoothstep curve, `3t² - 2t³`. `t` is clamped to 0.0 to 1.0 first.
+    ///
+    /// This starts and ends gently, which often looks more natural in animations than a plain
+    /// lerp.
+    #[inline]
+    pub fn smoothstep(self, other: LinearColor, t: f32) -> LinearColor {
+        let t = t.clamp(0.0, 1.0);
+
+        self.lerp(other, t * t * (3.0 - 2.0 * t))
+    }
+
+    /// Linearly interpolates between this color and `other`, like [lerp](Self::lerp), but with
+    /// a separate `t` for the color channels and for alpha. This lets animations fade alpha on a
+    /// different curve than the color, like an eased color change with a linear fade.
+    #[inline]
+    pub fn lerp_split(self, other: LinearColor, rgb_t: f32, alpha_t: f32) -> LinearColor {
+        LinearColor {
+            a: self.a + (other.a - self.a) * alpha_t,
+            ..self.lerp(other, rgb_t)
+        }
+    }
+
+    /// Mixes this color with `other`, using a weight for each. The weights are normalized by
+    /// their total, so `(1.0, 3.0)` is the same as `(0.25, 0.75)`. If the weights total to zero,
+    /// this gives the even average of the two colors.
+    ///
+    /// This is often more natural than [lerp](Self::lerp) when combining samples which each
+    /// come with a confidence.
+    pub fn mix_weighted(self, other: LinearColor, self_weight: f32, other_weight: f32) -> LinearColor {
+        let total = self_weight + other_weight;
+        if total == 0.0 {
+            return self.lerp(other, 0.5);
+        }
+
+        LinearColor {
+            r: (self.r * self_weight + other.r * other_weight) / total,
+            g: (self.g * self_weight + other.g * other_weight) / total,
+            b: (self.b * self_weight + other.b * other_weight) / total,
+            a: (self.a * self_weight + other.a * other_weight) / total,
+        }
+    }
+
+    /// Adds `other` to this color per channel, clamping each channel to 0.0 to 1.0 afterward.
+    /// This is for accumulating light into an SDR target, where anything brighter than white
+    /// should just be white, rather than turning into HDR values. Alpha is added and clamped the
+    /// same way, so it accumulates coverage.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn saturating_add(self, other: LinearColor) -> LinearColor {
+        LinearColor {
+            r: (self.r + other.r).clamp(0.0, 1.0),
+            g: (self.g + other.g).clamp(0.0, 1.0),
+            b: (self.b + other.b).clamp(0.0, 1.0),
+            a: (self.a + other.a).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Moves each channel of this color (including alpha) toward `target` by at most
+    /// `max_delta`, landing exactly on `target` once it's within reach. This is the classic
+    /// "move toward" helper for animating colors a little bit every frame.
+    ///
+    /// Since this works in linear space, transitions look even, rather than rushing through
+    /// the darks like they do when stepping encoded colors.
+    pub fn step_toward(self, target: LinearColor, max_delta: f32) -> LinearColor {
+        fn step(current: f32, target: f32, max_delta: f32) -> f32 {
+            let diff = target - current;
+            if diff.abs() <= max_delta {
+                target
+            } else {
+                current + max_delta.copysign(diff)
+            }
+        }
+
+        LinearColor {
+            r: step(self.r, target.r, max_delta),
+            g: step(self.g, target.g, max_delta),
+            b: step(self.b, target.b, max_delta),
+            a: step(self.a, target.a, max_delta),
+        }
+    }
+}
+
+impl EncodedColor {
+    /// Linearly interpolates between this color and `other` by blending the u8s directly, per
+    /// channel (including alpha). A `t` of 0.0 gives `self` and a `t` of 1.0 gives `other`, and
+    /// `t` is clamped to that range. Each channel is rounded to the nearest step.
+    ///
+    /// **This is intentionally wrong.** Blending encoded values doesn't blend the light, so the
+    /// middle of a gradient comes out too dark (and muddy between saturated colors). It's here
+    /// because plenty of older engines and tools (like Unity's `Color32.Lerp`) blend this way,
+    /// and sometimes you need to match them exactly. Otherwise, blend in linear space with
+    /// [LinearColor::lerp].
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// let gray = EncodedColor::BLACK.lerp_encoded(EncodedColor::WHITE, 0.5);
+    /// assert_eq!(gray, EncodedColor::new(128, 128, 128, 255));
+    /// ```
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    #[inline]
+    pub fn lerp_encoded(self, other: EncodedColor, t: f32) -> EncodedColor {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t + 0.5) as u8;
+
+        EncodedColor::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+            channel(self.a, other.a),
+        )
+    }
+
+    /// Finds the average color of `pixels`, such as the average color of a texture. Alpha is
+    /// averaged along with the other channels, and if `pixels` is empty, this gives
+    /// [CLEAR](Self::CLEAR).
+    ///
+    /// The colors are averaged in linear space, so this is gamma-correct: the average of black
+    /// and white is (188, 188, 188), not the (128, 128, 128) you get by averaging the bytes.
+    ///
+    /// Transparent pixels count just as much as opaque ones here, so for images with transparency,
+    /// you probably want [average_alpha_weighted](Self::average_alpha_weighted).
+    pub fn average_of(pixels: &[EncodedColor]) -> EncodedColor {
+        if pixels.is_empty() {
+            return EncodedColor::CLEAR;
+        }
+
+        // we sum in f64s so that large images don't lose precision.
+        let mut sum = [0.0f64; 4];
+        for pixel in pixels {
+            let linear = pixel.to_linear();
+            sum[0] += linear.r as f64;
+            sum[1] += linear.g as f64;
+            sum[2] += linear.b as f64;
+            sum[3] += linear.a as f64;
+        }
+
+        let count = pixels.len() as f64;
+        LinearColor {
+            r: (sum[0] / count) as f32,
+            g: (sum[1] / count) as f32,
+            b: (sum[2] / count) as f32,
+            a: (sum[3] / count) as f32,
+        }
+        .to_encoded_space()
+    }
+
+    /// Finds the average color of `pixels`, weighting each pixel's color by its alpha. This is
+    /// the right way to average an image with transparent parts: a fully transparent pixel has no
+    /// color to contribute, so it doesn't pull the average toward whatever rgb it happens to hold
+    /// (usually black). It still counts toward the average alpha, though.
+    ///
+    /// Like [average_of](Self::average_of), this works in linear space, and gives
+    /// [CLEAR](Self::CLEAR) if `pixels` is empty or entirely transparent.
+    pub fn average_alpha_weighted(pixels: &[EncodedColor]) -> EncodedColor {
+        let mut sum = [0.0f64; 4];
+        for pixel in pixels {
+            let linear = pixel.to_linear();
+            let alpha = linear.a as f64;
+
+            sum[0] += linear.r as f64 * alpha;
+            sum[1] += linear.g as f64 * alpha;
+            sum[2] += linear.b as f64 * alpha;
+            sum[3] += alpha;
+        }
+
+        if sum[3] == 0.0 {
+            return EncodedColor::CLEAR;
+        }
+
+        LinearColor {
+            r: (sum[0] / sum[3]) as f32,
+            g: (sum[1] / sum[3]) as f32,
+            b: (sum[2] / sum[3]) as f32,
+            a: (sum[3] / pixels.len() as f64) as f32,
+        }
+        .to_encoded_space()
+    }
+
+    /// Builds a color ramp of `count` colors, evenly spread across `stops`, like a 256 entry
+    /// ramp for a chart out of a few key colors. The first entry is the first stop and the
+    /// last entry is the last stop, with the other stops evenly spaced in between.
+    ///
+    /// The stops are blended in linear space, like [lerp](LinearColor::lerp), and then encoded
+    /// again. If `stops` is empty, every entry is [CLEAR](Self::CLEAR).
+    #[cfg(feature = "alloc")]
+    pub fn gradient_stops(stops: &[EncodedColor], count: usize) -> Vec<EncodedColor> {
+        let linear: Vec<LinearColor> = stops.iter().map(|c| c.to_linear()).collect();
+
+        (0..count)
+            .map(|i| match linear.len() {
+                0 => EncodedColor::CLEAR,
+                1 => stops[0],
+                len => {
+                    // where we are across all of the stops, from 0.0 to `len - 1`
+                    let position = if count == 1 {
+                        0.0
+                    } else {
+                        (i * (len - 1)) as f32 / (count - 1) as f32
+                    };
+                    let segment = (position as usize).min(len - 2);
+
+                    linear[segment]
+                        .lerp(linear[segment + 1], position - segment as f32)
+                        .to_encoded_space()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Adds the colors per channel, including alpha, without clamping, which is how light
+/// accumulates. To clamp to white, use [saturating_add](LinearColor::saturating_add).
+impl Add for LinearColor {
+    type Output = LinearColor;
+
+    #[inline]
+    fn add(self, other: LinearColor) -> LinearColor {
+        LinearColor {
+            r: self.r + other.r,
+            g: self.g + other.g,
+            b: self.b + other.b,
+            a: self.a + other.a,
+        }
+    }
+}
+
+impl AddAssign for LinearColor {
+    #[inline]
+    fn add_assign(&mut self, other: LinearColor) {
+        *self = *self + other;
+    }
+}
+
+/// Sums the colors per channel, including alpha, starting from all zeros, like adding them
+/// up with [Add].
+impl Sum for LinearColor {
+    fn sum<I: Iterator<Item = LinearColor>>(iter: I) -> Self {
+        iter.fold(LinearColor::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a LinearColor> for LinearColor {
+    fn sum<I: Iterator<Item = &'a LinearColor>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// The linear-space negative, `1.0 - channel` on red, green, and blue, keeping alpha. This is
+/// **not** a sign flip: `-LinearColor::new(0.0, 0.0, 0.0, 1.0)` is white, not a color with
+/// negative channels.
+///
+/// This is the linear complement, so it's different from [invert](EncodedColor::invert), which
+/// complements the encoded bytes. Mid gray (about `0.214` in linear) negates to about `0.786`,
+/// which looks much lighter than the `128` that inverting `127` gives.
+impl Neg for LinearColor {
+    type Output = LinearColor;
+
+    #[inline]
+    fn neg(self) -> LinearColor {
+        LinearColor {
+            r: 1.0 - self.r,
+            g: 1.0 - self.g,
+            b: 1.0 - self.b,
+            a: self.a,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::close;
+
+    #[test]
+    fn lerp() {
+        let a = LinearColor::new(0.0, 0.5, 1.0, 1.0);
+        let b = LinearColor::new(1.0, 0.5, 0.0, 0.0);
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert!(close(a.lerp(b, 0.25), LinearColor::new(0.25, 0.5, 0.75, 0.75)));
+    }
+
+    #[test]
+    fn lerp_split() {
+        let a = LinearColor::new(0.0, 0.0, 0.0, 0.0);
+        let b = LinearColor::new(1.0, 1.0, 1.0, 1.0);
+
+        assert!(close(
+            a.lerp_split(b, 0.25, 0.75),
+            LinearColor::new(0.25, 0.25, 0.25, 0.75)
+        ));
+        assert!(close(a.lerp_split(b, 1.0, 0.0), LinearColor::new(1.0, 1.0, 1.0, 0.0)));
+        assert!(close(a.lerp_split(b, 0.0, 1.0), LinearColor::new(0.0, 0.0, 0.0, 1.0)));
+        assert_eq!(a.lerp_split(b, 0.3, 0.3), a.lerp(b, 0.3));
+    }
+
+    #[test]
+    fn mix_weighted() {
+        let a = LinearColor::new(0.2, 0.4, 0.6, 1.0);
+        let b = LinearColor::new(0.8, 0.1, 0.0, 0.5);
+
+        assert!(close(a.mix_weighted(b, 2.0, 2.0), a.lerp(b, 0.5)));
+        assert!(close(a.mix_weighted(b, 1.0, 3.0), a.lerp(b, 0.75)));
+        assert!(close(a.mix_weighted(b, 0.0, 1.0), b));
+        assert!(close(a.mix_weighted(b, 1.0, 0.0), a));
+        assert!(close(a.mix_weighted(b, 0.0, 0.0), a.lerp(b, 0.5)));
+    }
+
+    #[test]
+    fn add_and_sum() {
+        let a = LinearColor::new(0.8, 0.1, 0.25, 0.5);
+        let b = LinearColor::new(0.5, 0.0, 0.5, 1.0);
+        let c = LinearColor::new(0.25, 0.125, 0.0, 0.25);
+
+        assert_eq!(a + b, LinearColor::new(1.3, 0.1, 0.75, 1.5));
+
+        let mut accumulated = a;
+        accumulated += b;
+        accumulated += c;
+
+        let colors = [a, b, c];
+        assert_eq!(colors.iter().copied().sum::<LinearColor>(), accumulated);
+        assert_eq!(colors.iter().sum::<LinearColor>(), a + b + c);
+        assert_eq!(
+            core::iter::empty::<LinearColor>().sum::<LinearColor>(),
+            LinearColor::new(0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn neg() {
+        let color = LinearColor::new(0.25, 0.5, 1.0, 0.75);
+        assert_eq!(-color, LinearColor::new(0.75, 0.5, 0.0, 0.75));
+        assert_eq!(
+            -LinearColor::new(0.0, 0.0, 0.0, 1.0),
+            LinearColor::new(1.0, 1.0, 1.0, 1.0)
+        );
+
+        for color in [
+            color,
+            LinearColor::new(0.1, 0.3, 0.7, 0.2),
+            EncodedColor::new(107, 158, 190, 255).to_linear(),
+            EncodedColor::CLEAR.to_linear(),
+        ] {
+            assert!(close(-(-color), color), "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn saturating_add() {
+        let bright = LinearColor::new(0.8, 0.7, 0.9, 0.6);
+        assert_eq!(bright.saturating_add(bright), LinearColor::new(1.0, 1.0, 1.0, 1.0));
+
+        let dim = LinearColor::new(0.1, 0.2, 0.0, 0.25);
+        assert!(close(dim.saturating_add(dim), LinearColor::new(0.2, 0.4, 0.0, 0.5)));
+        assert!(close(bright.saturating_add(dim), LinearColor::new(0.9, 0.9, 0.9, 0.85)));
+
+        let negative = LinearColor::new(-0.5, 0.0, 0.0, 0.0);
+        assert_eq!(negative.saturating_add(dim).r, 0.0);
+    }
+
+    #[test]
+    fn step_toward() {
+        let start = LinearColor::new(0.0, 1.0, 0.5, 0.2);
+        let target = LinearColor::new(1.0, 0.0, 0.5, 0.25);
+
+        let mut current = start;
+        let mut steps = 0;
+        while current != target {
+            let next = current.step_toward(target, 0.1);
+
+            // every channel moves toward the target, and never past it
+            let channels = |c: LinearColor| -> [f32; 4] { c.into() };
+            for ((prev, next), target) in channels(current).iter().zip(channels(next)).zip(channels(target)) {
+                assert!((target - next).abs() <= (target - prev).abs());
+                assert!((next - prev).abs() <= 0.1 + f32::EPSILON);
+                assert!((target - prev) * (target - next) >= 0.0);
+            }
+
+            current = next;
+            steps += 1;
+            assert!(steps <= 10);
+        }
+        assert_eq!(steps, 10);
+        assert_eq!(target.step_toward(target, 0.1), target);
+    }
+
+    #[test]
+    fn average_of() {
+        // gamma-correct, so not 128!
+        let gray = EncodedColor::average_of(&[EncodedColor::BLACK, EncodedColor::WHITE]);
+        assert_eq!(gray, EncodedColor::new(188, 188, 188, 255));
+
+        assert_eq!(EncodedColor::average_of(&[]), EncodedColor::CLEAR);
+        assert_eq!(EncodedColor::average_of(&[EncodedColor::TEAL; 100]), EncodedColor::TEAL);
+
+        let average = EncodedColor::average_of(&[EncodedColor::RED, EncodedColor::BLUE, EncodedColor::CLEAR]);
+        assert_eq!(average, EncodedColor::new(156, 0, 156, 170));
+    }
+
+    #[test]
+    fn average_alpha_weighted() {
+        let mut image = [EncodedColor::CLEAR; 8];
+        image[0] = EncodedColor::WHITE;
+        image[5] = EncodedColor::WHITE;
+
+        // the transparent black pixels don't darken anything...
+        let average = EncodedColor::average_alpha_weighted(&image);
+        assert_eq!(average, EncodedColor::new(255, 255, 255, 63));
+
+        // ...unlike a plain average.
+        assert_eq!(EncodedColor::average_of(&image), EncodedColor::new(137, 137, 137, 63));
+
+        // partial alpha weights partially
+        let average =
+            EncodedColor::average_alpha_weighted(&[EncodedColor::RED.with_a(255), EncodedColor::BLUE.with_a(85)]);
+        assert!(average.r > average.b);
+        assert_eq!(average.a, 170);
+
+        assert_eq!(EncodedColor::average_alpha_weighted(&[]), EncodedColor::CLEAR);
+        assert_eq!(
+            EncodedColor::average_alpha_weighted(&[EncodedColor::RED_CLEAR.with_a(0); 4]),
+            EncodedColor::CLEAR
+        );
+        assert_eq!(
+            EncodedColor::average_alpha_weighted(&[EncodedColor::TEAL; 3]),
+            EncodedColor::TEAL
+        );
+    }
+
+    #[test]
+    fn barycentric() {
+        let colors = [
+            LinearColor::new(0.9, 0.0, 0.0, 1.0),
+            LinearColor::new(0.0, 0.6, 0.0, 0.5),
+            LinearColor::new(0.0, 0.0, 0.3, 0.0),
+        ];
+
+        assert_eq!(LinearColor::barycentric(colors, [1.0, 0.0, 0.0]), colors[0]);
+        assert_eq!(LinearColor::barycentric(colors, [0.0, 0.0, 1.0]), colors[2]);
+
+        let third = 1.0 / 3.0;
+        let average = LinearColor::barycentric(colors, [third; 3]);
+        assert!(close(average, LinearColor::new(0.3, 0.2, 0.1, 0.5)), "{:?}", average);
+
+        // on an edge, it's a lerp
+        let edge = LinearColor::barycentric(colors, [0.25, 0.75, 0.0]);
+        assert!(close(edge, colors[0].lerp(colors[1], 0.75)), "{:?}", edge);
+    }
+
+    #[test]
+    fn smoothstep() {
+        let a = LinearColor::new(0.1, 0.2, 0.9, 1.0);
+        let b = LinearColor::new(0.8, 0.6, 0.0, 0.5);
+
+        assert_eq!(a.smoothstep(b, 0.0), a);
+        assert_eq!(a.smoothstep(b, 1.0), b);
+        assert_eq!(a.smoothstep(b, 0.5), a.lerp(b, 0.5));
+
+        // it eases, so it's behind a lerp early on and ahead of it late
+        assert!(a.smoothstep(b, 0.25).r < a.lerp(b, 0.25).r);
+        assert!(a.smoothstep(b, 0.75).r > a.lerp(b, 0.75).r);
+
+        assert_eq!(a.smoothstep(b, -1.0), a);
+        assert_eq!(a.smoothstep(b, 2.0), b);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn gradient_stops() {
+        let stops = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::RED,
+            EncodedColor::new(30, 30, 30, 128),
+            EncodedColor::WHITE,
+        ];
+
+        // 3 steps between each stop, so the stops land on every third entry
+        let ramp = EncodedColor::gradient_stops(&stops, 10);
+        assert_eq!(ramp.len(), 10);
+        for (i, stop) in stops.iter().enumerate() {
+            assert_eq!(ramp[i * 3], *stop);
+        }
+
+        // and in between, it's the linear blend
+        assert_eq!(
+            ramp[1],
+            stops[0]
+                .to_linear()
+                .lerp(stops[1].to_linear(), 1.0 / 3.0)
+                .to_encoded_space()
+        );
+
+        let ramp = EncodedColor::gradient_stops(&stops, 256);
+        assert_eq!(ramp.len(), 256);
+        assert_eq!(ramp[0], stops[0]);
+        assert_eq!(ramp[255], stops[3]);
+
+        assert_eq!(EncodedColor::gradient_stops(&stops, 1), [stops[0]]);
+        assert!(EncodedColor::gradient_stops(&stops, 0).is_empty());
+        assert_eq!(EncodedColor::gradient_stops(&stops[..1], 3), [stops[0]; 3]);
+        assert_eq!(EncodedColor::gradient_stops(&[], 2), [EncodedColor::CLEAR; 2]);
+    }
+
+    #[test]
+    fn lerp_encoded() {
+        let black = EncodedColor::BLACK;
+        let white = EncodedColor::WHITE;
+
+        let mid = black.lerp_encoded(white, 0.5);
+        assert!(mid.r == 127 || mid.r == 128, "{:?}", mid);
+        assert_eq!(mid, EncodedColor::new(mid.r, mid.r, mid.r, 255));
+
+        // which is darker than blending the light
+        assert!(mid.r < black.to_linear().lerp(white.to_linear(), 0.5).to_encoded_space().r);
+
+        let sky = EncodedColor::new(107, 158, 190, 40);
+        assert_eq!(sky.lerp_encoded(white, 0.0), sky);
+        assert_eq!(sky.lerp_encoded(white, 1.0), white);
+        assert_eq!(sky.lerp_encoded(white, -3.0), sky);
+        assert_eq!(sky.lerp_encoded(white, 7.0), white);
+        assert_eq!(white.lerp_encoded(sky, 0.25), EncodedColor::new(218, 231, 239, 201));
+    }
+}
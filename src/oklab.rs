@@ -0,0 +1,432 @@
+use crate::{EncodedColor, LinearColor, math};
+
+/// A color in the [Oklab](https://bottosson.github.io/posts/oklab/) color space.
+///
+/// Oklab is a *perceptual* color space: distances in it roughly match how different two
+/// colors look to a person. That makes it the right space for comparing colors, and for
+/// changing lightness without messing up the hue. It is not a good space to blend light in --
+/// use [LinearColor] for that.
+///
+/// Oklab has no alpha, so the conversions into and out of it take the alpha separately.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Oklab {
+    /// The perceived lightness, from 0.0 (black) to 1.0 (white).
+    pub l: f32,
+
+    /// How green (negative) or red (positive) the color is.
+    pub a: f32,
+
+    /// How blue (negative) or yellow (positive) the color is.
+    pub b: f32,
+}
+
+impl Oklab {
+    /// Creates a new Oklab color.
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+
+    /// The perceptual distance between two colors, often called ΔE. A distance of about `0.02`
+    /// is right on the edge of noticeable, while black and white are `1.0` apart.
+    pub fn distance(self, other: Oklab) -> f32 {
+        math::sqrt(self.distance_squared(other))
+    }
+
+    /// The squared perceptual distance between two colors. When you only need to know which
+    /// color is *closer*, this saves a square root over [distance](Self::distance).
+    pub fn distance_squared(self, other: Oklab) -> f32 {
+        let l = self.l - other.l;
+        let a = self.a - other.a;
+        let b = self.b - other.b;
+
+        l * l + a * a + b * b
+    }
+}
+
+/// A color in the Oklch color space, which is [Oklab] in polar coordinates.
+///
+/// This is the friendliest way to tweak a color by hand: `l` is the lightness, `c` is the
+/// chroma ("how colorful"), and `h` is the hue angle. Changing any one of these leaves the
+/// other two looking the same.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Oklch {
+    /// The perceived lightness, from 0.0 (black) to 1.0 (white).
+    pub l: f32,
+
+    /// The chroma, or how colorful the color is. 0.0 is a gray, and the most colorful colors
+    /// in sRGB are a bit above 0.3.
+    pub c: f32,
+
+    /// The hue, in degrees from 0.0 to 360.0. For grays, the hue is meaningless.
+    pub h: f32,
+}
+
+impl Oklch {
+    /// Creates a new Oklch color.
+    pub const fn new(l: f32, c: f32, h: f32) -> Self {
+        Self { l, c, h }
+    }
+
+    /// Converts this color into [Oklab].
+    pub fn to_oklab(self) -> Oklab {
+        let h = self.h.to_radians();
+
+        Oklab {
+            l: self.l,
+            a: self.c * math::cos(h),
+            b: self.c * math::sin(h),
+        }
+    }
+}
+
+impl Oklab {
+    /// Converts this color into [Oklch].
+    pub fn to_oklch(self) -> Oklch {
+        let h = math::atan2(self.b, self.a).to_degrees();
+
+        Oklch {
+            l: self.l,
+            c: math::sqrt(self.a * self.a + self.b * self.b),
+            h: if h < 0.0 { h + 360.0 } else { h },
+        }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(o: Oklab) -> Self {
+        o.to_oklch()
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(o: Oklch) -> Self {
+        o.to_oklab()
+    }
+}
+
+// these matrices are copied as-is from the reference implementation.
+#[allow(clippy::excessive_precision)]
+impl LinearColor {
+    /// Converts this color into the [Oklab] color space, dropping alpha.
+    pub fn to_oklab(self) -> Oklab {
+        let l = 0.4122214708 * self.r + 0.5363325363 * self.g + 0.0514459929 * self.b;
+        let m = 0.2119034982 * self.r + 0.6806995451 * self.g + 0.1073969566 * self.b;
+        let s = 0.0883024619 * self.r + 0.2817188376 * self.g + 0.6299787005 * self.b;
+
+        let l = math::cbrt(l);
+        let m = math::cbrt(m);
+        let s = math::cbrt(s);
+
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+
+    /// Converts an [Oklab] color back into linear space, with the given alpha.
+    ///
+    /// Plenty of Oklab colors are outside of what sRGB can show, so the channels of
+    /// the output can be outside of 0.0 to 1.0.
+    pub fn from_oklab(lab: Oklab, a: f32) -> Self {
+        let l = lab.l + 0.3963377774 * lab.a + 0.2158037573 * lab.b;
+        let m = lab.l - 0.1055613458 * lab.a - 0.0638541728 * lab.b;
+        let s = lab.l - 0.0894841775 * lab.a - 1.2914855480 * lab.b;
+
+        let l = l * l * l;
+        let m = m * m * m;
+        let s = s * s * s;
+
+        LinearColor {
+            r: 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            g: -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            b: -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+            a,
+        }
+    }
+}
+
+impl LinearColor {
+    /// Converts this color into the [Oklch] color space, dropping alpha.
+    pub fn to_oklch(self) -> Oklch {
+        self.to_oklab().to_oklch()
+    }
+
+    /// Converts an [Oklch] color back into linear space, with the given alpha.
+    ///
+    /// Like with [from_oklab](Self::from_oklab), the channels of the output can be outside
+    /// of 0.0 to 1.0. Use [gamut_clip_oklch](Self::gamut_clip_oklch) to bring them back in.
+    pub fn from_oklch(lch: Oklch, a: f32) -> Self {
+        Self::from_oklab(lch.to_oklab(), a)
+    }
+
+    /// Brings a color which is outside of sRGB (any of r, g, or b outside of 0.0 to 1.0) back
+    /// inside it, by reducing its Oklch chroma until it fits. Lightness and hue are kept, so the
+    /// result looks like a less colorful version of the original, rather than the hue shifts
+    /// and flat patches you get by clamping each channel. Alpha is kept as-is.
+    ///
+    /// Lightness outside of 0.0 to 1.0 can't be shown at any chroma, so it's clamped first.
+    pub fn gamut_clip_oklch(self) -> LinearColor {
+        if self.in_gamut() {
+            return self;
+        }
+
+        let lch = self.to_oklch();
+        let l = lch.l.clamp(0.0, 1.0);
+
+        // every lightness is in gamut at zero chroma, so we binary search between that
+        // and the chroma we started with.
+        let mut low = 0.0;
+        let mut high = lch.c;
+        for _ in 0..24 {
+            let c = (low + high) / 2.0;
+            if LinearColor::from_oklch(Oklch { l, c, h: lch.h }, self.a).in_gamut() {
+                low = c;
+            } else {
+                high = c;
+            }
+        }
+
+        let output = LinearColor::from_oklch(Oklch { l, c: low, h: lch.h }, self.a);
+
+        // and then clean up any float fuzz left on the edges.
+        LinearColor {
+            r: output.r.clamp(0.0, 1.0),
+            g: output.g.clamp(0.0, 1.0),
+            b: output.b.clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+
+    fn in_gamut(self) -> bool {
+        const EPSILON: f32 = 0.0001;
+        let range = -EPSILON..=1.0 + EPSILON;
+
+        range.contains(&self.r) && range.contains(&self.g) && range.contains(&self.b)
+    }
+}
+
+impl EncodedColor {
+    /// Converts this color into the [Oklab] color space, dropping alpha. This goes
+    /// through [LinearColor].
+    pub fn to_oklab(self) -> Oklab {
+        self.to_linear().to_oklab()
+    }
+
+    /// Converts an [Oklab] color into an encoded color with the given alpha.
+    /// Colors outside of sRGB are clamped.
+    pub fn from_oklab(lab: Oklab, a: u8) -> Self {
+        LinearColor::from_oklab(lab, 1.0).to_encoded_space().with_a(a)
+    }
+
+    /// Converts this color into the [Oklch] color space, dropping alpha.
+    pub fn to_oklch(self) -> Oklch {
+        self.to_oklab().to_oklch()
+    }
+
+    /// Converts an [Oklch] color into an encoded color with the given alpha.
+    /// Colors outside of sRGB are clamped.
+    pub fn from_oklch(lch: Oklch, a: u8) -> Self {
+        Self::from_oklab(lch.to_oklab(), a)
+    }
+
+    /// The perceptual difference (ΔE) between this color and `other`, measured in [Oklab].
+    /// Alpha is ignored. See [Oklab::distance] for the scale.
+    pub fn delta_e(self, other: EncodedColor) -> f32 {
+        self.to_oklab().distance(other.to_oklab())
+    }
+
+    /// Checks if this color and `other` look the same, within a tolerance of `delta_e`, in
+    /// [Oklab]. Alpha is ignored.
+    ///
+    /// This is a better tool than comparing bytes when you want "visually the same", like
+    /// checking a JPEG-compressed image against its original: the same byte difference is
+    /// much more visible in some colors than others. A `delta_e` of `0.02` is about the smallest
+    /// difference people can notice.
+    pub fn perceptually_close(self, other: EncodedColor, delta_e: f32) -> bool {
+        self.delta_e(other) <= delta_e
+    }
+
+    /// How different this color and `other` look, from 0.0 (the same) to 100.0 (black and
+    /// white), for showing people a "how different" number. Alpha is ignored.
+    ///
+    /// This is the [delta_e](Self::delta_e) between them, scaled so that black and white, the
+    /// most different pair of colors in sRGB, are 100.0 apart. It's perceptual, so the same
+    /// percent means about as much of a difference anywhere in the gamut.
+    pub fn difference_percent(self, other: EncodedColor) -> f32 {
+        // black to white, in Oklab
+        const MAX_DELTA_E: f32 = 1.0;
+
+        (self.delta_e(other) / MAX_DELTA_E * 100.0).min(100.0)
+    }
+
+    /// Creates a new color, overriding the Oklch lightness with the provided value and keeping
+    /// chroma, hue, and alpha.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn with_oklch_lightness(self, l: f32) -> Self {
+        Self::from_oklch(Oklch { l, ..self.to_oklch() }, self.a)
+    }
+
+    /// Creates a new color, overriding the Oklch chroma with the provided value and keeping
+    /// lightness, hue, and alpha.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn with_oklch_chroma(self, c: f32) -> Self {
+        Self::from_oklch(Oklch { c, ..self.to_oklch() }, self.a)
+    }
+
+    /// Creates a new color, overriding the Oklch hue (in degrees) with the provided value and
+    /// keeping lightness, chroma, and alpha.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn with_oklch_hue(self, h: f32) -> Self {
+        Self::from_oklch(Oklch { h, ..self.to_oklch() }, self.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oklab() {
+        fn close(a: Oklab, b: Oklab) -> bool {
+            a.distance(b) < 0.001
+        }
+
+        // reference values from https://bottosson.github.io/posts/oklab/
+        assert!(close(EncodedColor::WHITE.to_oklab(), Oklab::new(1.0, 0.0, 0.0)));
+        assert!(close(EncodedColor::BLACK.to_oklab(), Oklab::new(0.0, 0.0, 0.0)));
+        assert!(close(
+            EncodedColor::RED.to_oklab(),
+            Oklab::new(0.627955, 0.224863, 0.125846)
+        ));
+        assert!(close(
+            EncodedColor::GREEN.to_oklab(),
+            Oklab::new(0.86644, -0.233888, 0.179498)
+        ));
+        assert!(close(
+            EncodedColor::BLUE.to_oklab(),
+            Oklab::new(0.452014, -0.032457, -0.311528)
+        ));
+
+        for c in [
+            EncodedColor::WHITE,
+            EncodedColor::BLACK,
+            EncodedColor::TEAL,
+            EncodedColor::new(107, 158, 190, 20),
+        ] {
+            assert_eq!(EncodedColor::from_oklab(c.to_oklab(), c.a), c);
+        }
+
+        assert!((EncodedColor::BLACK.to_oklab().distance(EncodedColor::WHITE.to_oklab()) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn oklch() {
+        let lch = EncodedColor::RED.to_oklch();
+        assert!((lch.l - 0.627955).abs() < 0.001);
+        assert!((lch.c - 0.257683).abs() < 0.001);
+        assert!((lch.h - 29.2339).abs() < 0.01);
+        assert_eq!(EncodedColor::from_oklch(lch, 255), EncodedColor::RED);
+
+        // negative b lands in the bottom half of the circle
+        assert!((EncodedColor::BLUE.to_oklch().h - 264.052).abs() < 0.01);
+        assert!(EncodedColor::WHITE.to_oklch().c < 0.001);
+    }
+
+    #[test]
+    fn oklch_setters() {
+        // a color comfortably inside of sRGB, so small changes don't clip
+        let base = EncodedColor::new(107, 158, 190, 200);
+        let lch = base.to_oklch();
+
+        // rounding to u8s costs us a little precision, so we compare loosely
+        let check = |c: EncodedColor, l: f32, chroma: f32, h: f32| {
+            let out = c.to_oklch();
+            assert!((out.l - l).abs() < 0.005, "{:?} vs {}", out, l);
+            assert!((out.c - chroma).abs() < 0.005, "{:?} vs {}", out, chroma);
+            assert!((out.h - h).abs() < 1.0, "{:?} vs {}", out, h);
+            assert_eq!(c.a, 200);
+        };
+
+        check(base.with_oklch_lightness(0.5), 0.5, lch.c, lch.h);
+        check(base.with_oklch_chroma(0.03), lch.l, 0.03, lch.h);
+        check(base.with_oklch_hue(180.0), lch.l, lch.c, 180.0);
+    }
+
+    #[test]
+    fn gamut_clip_oklch() {
+        // well outside of sRGB -- no sRGB green is nearly this colorful.
+        let wanted = Oklch::new(0.7, 0.4, 150.0);
+        let linear = LinearColor::from_oklch(wanted, 0.5);
+        assert!(!linear.in_gamut());
+
+        let clipped = linear.gamut_clip_oklch();
+        assert!(clipped.in_gamut());
+        assert_eq!(clipped.a, 0.5);
+
+        let got = clipped.to_oklch();
+        assert!((got.l - wanted.l).abs() < 0.001);
+        assert!((got.h - wanted.h).abs() < 0.5);
+        assert!(got.c < wanted.c && got.c > 0.1);
+
+        // in gamut colors are untouched
+        let linear = EncodedColor::new(107, 158, 190, 255).to_linear();
+        assert_eq!(linear.gamut_clip_oklch(), linear);
+
+        // and lightness past white is just white
+        let clipped = LinearColor::from_oklch(Oklch::new(1.2, 0.1, 40.0), 1.0).gamut_clip_oklch();
+        assert_eq!(clipped.to_encoded_space(), EncodedColor::WHITE);
+    }
+
+    #[test]
+    fn perceptually_close() {
+        let a = EncodedColor::new(107, 158, 190, 255);
+        let b = EncodedColor::new(110, 158, 188, 255);
+
+        let delta_e = a.delta_e(b);
+        assert!(delta_e > 0.0 && delta_e < 0.02);
+        assert!(a.perceptually_close(b, delta_e + 0.0001));
+        assert!(a.perceptually_close(b, delta_e));
+        assert!(!a.perceptually_close(b, delta_e - 0.0001));
+        assert!(b.perceptually_close(a, 0.02));
+
+        assert!(a.perceptually_close(a.with_a(0), 0.0));
+        assert!(!EncodedColor::BLACK.perceptually_close(EncodedColor::WHITE, 0.99));
+        assert!(EncodedColor::BLACK.perceptually_close(EncodedColor::WHITE, 1.01));
+
+        // the same byte difference matters more in the darks than in the lights
+        let dark = EncodedColor::new(10, 10, 10, 255).delta_e(EncodedColor::new(14, 14, 14, 255));
+        let light = EncodedColor::new(240, 240, 240, 255).delta_e(EncodedColor::new(244, 244, 244, 255));
+        assert!(dark > light);
+    }
+
+    #[test]
+    fn difference_percent() {
+        let sky = EncodedColor::new(107, 158, 190, 255);
+        assert_eq!(sky.difference_percent(sky), 0.0);
+        assert_eq!(sky.difference_percent(sky.with_a(0)), 0.0);
+
+        let black_white = EncodedColor::BLACK.difference_percent(EncodedColor::WHITE);
+        assert!((black_white - 100.0).abs() < 0.1, "{}", black_white);
+
+        // it's symmetric, and neighbors are only a little different
+        let near = EncodedColor::new(108, 158, 190, 255);
+        assert_eq!(sky.difference_percent(near), near.difference_percent(sky));
+        assert!(sky.difference_percent(near) < 1.0);
+
+        // and no pair of saturated colors is more different than black and white
+        let primaries = [
+            EncodedColor::RED,
+            EncodedColor::GREEN,
+            EncodedColor::BLUE,
+            EncodedColor::YELLOW,
+            EncodedColor::TEAL,
+            EncodedColor::new(255, 0, 255, 255),
+        ];
+        for a in primaries {
+            for b in primaries {
+                assert!(a.delta_e(b) < 1.0, "{:?} {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::EncodedColor;
+use core::fmt;
+
+/// A palette of up to 256 colors, for indexed color graphics, like tiles and sprites on older
+/// hardware. Colors are picked out of it with [PalettedColor]s.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    colors: [EncodedColor; 256],
+    len: usize,
+}
+
+/// How much each [Oklab](crate::Oklab) axis counts when finding the nearest color with
+/// [Palette::nearest_weighted]. Each difference is multiplied by its weight before it's
+/// squared, so a weight of 2.0 makes differences along that axis count twice as much.
+///
+/// The default weights are all 1.0, which is plain Oklab distance, like [Palette::nearest].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ChannelWeights {
+    /// The weight of differences in lightness.
+    pub l: f32,
+
+    /// The weight of differences along the green-red axis.
+    pub a: f32,
+
+    /// The weight of differences along the blue-yellow axis.
+    pub b: f32,
+}
+
+impl Default for ChannelWeights {
+    fn default() -> Self {
+        Self { l: 1.0, a: 1.0, b: 1.0 }
+    }
+}
+
+/// An index into a [Palette], which is how each pixel is stored in indexed color graphics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PalettedColor(pub u8);
+
+impl Palette {
+    /// Creates a palette out of `colors`, where the first color is index 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 256 colors, since that's all a [PalettedColor] can index.
+    pub fn new(colors: &[EncodedColor]) -> Self {
+        assert!(colors.len() <= 256, "a palette can only hold 256 colors");
+
+        let mut output = [EncodedColor::CLEAR; 256];
+        output[..colors.len()].copy_from_slice(colors);
+
+        Self {
+            colors: output,
+            len: colors.len(),
+        }
+    }
+
+    /// The colors in this palette, in index order.
+    pub fn colors(&self) -> &[EncodedColor] {
+        &self.colors[..self.len]
+    }
+
+    /// How many colors are in this palette.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this palette has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the color at `index`, if the palette has one there.
+    pub fn get(&self, index: PalettedColor) -> Option<EncodedColor> {
+        self.colors().get(index.0 as usize).copied()
+    }
+
+    /// Finds the index of the color in this palette which looks closest to `color`, comparing
+    /// in [Oklab](crate::Oklab). Alpha isn't considered. This is how to turn a full color image
+    /// into an indexed one, with a palette from something like `median_cut_palette` (with the
+    /// `alloc` feature).
+    ///
+    /// Returns `None` if the palette is empty.
+    pub fn nearest(&self, color: EncodedColor) -> Option<PalettedColor> {
+        self.nearest_weighted(color, ChannelWeights::default())
+    }
+
+    /// Like [nearest](Self::nearest), but with each Oklab axis weighted by `weights`. This is a
+    /// quality knob for dithering: weighting lightness more keeps the shading of an image
+    /// right, at the cost of hue, and the dither makes up the rest.
+    ///
+    /// Returns `None` if the palette is empty.
+    pub fn nearest_weighted(&self, color: EncodedColor, weights: ChannelWeights) -> Option<PalettedColor> {
+        let lab = color.to_oklab();
+
+        self.colors()
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let c = c.to_oklab();
+                let l = (c.l - lab.l) * weights.l;
+                let a = (c.a - lab.a) * weights.a;
+                let b = (c.b - lab.b) * weights.b;
+
+                (i, l * l + a * a + b * b)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| PalettedColor(i as u8))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl fmt::Debug for Palette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Palette").field(&self.colors()).finish()
+    }
+}
+
+impl PalettedColor {
+    /// Looks this index up in `palette`. If the palette doesn't have this many colors, this is
+    /// `None`.
+    pub fn resolve(&self, palette: &Palette) -> Option<EncodedColor> {
+        palette.get(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let palette = Palette::new(&[EncodedColor::BLACK, EncodedColor::WHITE, EncodedColor::RED]);
+        assert_eq!(palette.len(), 3);
+
+        assert_eq!(PalettedColor(0).resolve(&palette), Some(EncodedColor::BLACK));
+        assert_eq!(PalettedColor(2).resolve(&palette), Some(EncodedColor::RED));
+        assert_eq!(PalettedColor(3).resolve(&palette), None);
+        assert_eq!(PalettedColor(255).resolve(&palette), None);
+        assert_eq!(PalettedColor(0).resolve(&Palette::default()), None);
+
+        let full = Palette::new(&[EncodedColor::TEAL; 256]);
+        assert_eq!(PalettedColor(255).resolve(&full), Some(EncodedColor::TEAL));
+    }
+
+    #[test]
+    fn nearest_weighted() {
+        let gray = EncodedColor::new(128, 128, 128, 255);
+        let palette = Palette::new(&[
+            // darker, but still gray
+            EncodedColor::new(100, 100, 100, 255),
+            // just as light, but pinkish
+            EncodedColor::new(150, 118, 124, 255),
+        ]);
+        assert_eq!(palette.nearest(gray), Some(PalettedColor(1)));
+        assert_eq!(
+            palette.nearest_weighted(gray, ChannelWeights::default()),
+            palette.nearest(gray)
+        );
+
+        // caring more about lightness picks the pink, and caring less picks the gray
+        let lightness = ChannelWeights { l: 3.0, a: 1.0, b: 1.0 };
+        assert_eq!(palette.nearest_weighted(gray, lightness), Some(PalettedColor(1)));
+        let hue = ChannelWeights {
+            l: 0.25,
+            a: 1.0,
+            b: 1.0,
+        };
+        assert_eq!(palette.nearest_weighted(gray, hue), Some(PalettedColor(0)));
+
+        assert_eq!(Palette::default().nearest_weighted(gray, hue), None);
+    }
+
+    #[test]
+    fn nearest() {
+        let palette = Palette::new(&[EncodedColor::BLACK, EncodedColor::WHITE, EncodedColor::RED]);
+
+        assert_eq!(
+            palette.nearest(EncodedColor::new(20, 10, 10, 255)),
+            Some(PalettedColor(0))
+        );
+        assert_eq!(
+            palette.nearest(EncodedColor::new(230, 240, 250, 255)),
+            Some(PalettedColor(1))
+        );
+        assert_eq!(
+            palette.nearest(EncodedColor::new(200, 40, 30, 0)),
+            Some(PalettedColor(2))
+        );
+        assert_eq!(Palette::default().nearest(EncodedColor::RED), None);
+
+        // every color in the palette maps to itself
+        for (i, color) in palette.colors().iter().enumerate() {
+            assert_eq!(
+                palette.nearest(*color).unwrap().resolve(&palette),
+                Some(*color),
+                "{}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_colors() {
+        Palette::new(&[EncodedColor::TEAL; 257]);
+    }
+}
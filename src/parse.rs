@@ -0,0 +1,323 @@
+use crate::EncodedColor;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+/// The error returned when a string can't be parsed into an [EncodedColor].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseColorError {
+    /// The hex had the wrong number of digits. We accept 3, 4, 6, or 8 digits.
+    InvalidLength,
+
+    /// The hex contained a character which isn't a hex digit.
+    InvalidDigit {
+        /// The byte offset of the first bad character, in the whole string that was parsed.
+        position: usize,
+    },
+
+    /// A list of components had the wrong number of them. We accept 3 or 4.
+    InvalidComponentCount,
+
+    /// A component in a list wasn't an integer from 0 to 255.
+    InvalidComponent {
+        /// The byte offset of the start of the bad component, in the whole string that was
+        /// parsed.
+        position: usize,
+    },
+}
+
+impl ParseColorError {
+    /// The byte offset of the character which caused this error, in the whole string that was
+    /// parsed, for pointing at it in an error message. Errors about the whole string, like
+    /// [InvalidLength](Self::InvalidLength), don't have one.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            ParseColorError::InvalidLength | ParseColorError::InvalidComponentCount => None,
+            ParseColorError::InvalidDigit { position } | ParseColorError::InvalidComponent { position } => {
+                Some(*position)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidLength => f.write_str("hex colors must have 3, 4, 6, or 8 digits"),
+            ParseColorError::InvalidDigit { position } => {
+                write!(f, "invalid hex digit in color at byte {}", position)
+            }
+            ParseColorError::InvalidComponentCount => f.write_str("colors must have 3 or 4 components"),
+            ParseColorError::InvalidComponent { position } => {
+                write!(f, "invalid color component at byte {}, must be from 0 to 255", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColorError {}
+
+/// The error returned when [EncodedColor::parse_palette] finds a line which isn't a color.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsePaletteError {
+    /// The line, counting from 1.
+    pub line: usize,
+
+    /// What was wrong with the color on that line. Its position counts from the start of the
+    /// line.
+    pub error: ParseColorError,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParsePaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color on line {}: {}", self.line, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePaletteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Parses a hex color, like `#6b9ebe` or `#6B9EBEFF`.
+///
+/// This is meant for human input, so it's forgiving: surrounding whitespace is ignored,
+/// the `#` is optional, either case works, and a single trailing `;` or `,` (common when
+/// copying out of code or css) is dropped. The short `#rgb` and `#rgba` forms are supported
+/// too. When alpha isn't given, it's 255.
+impl FromStr for EncodedColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // where the digits start in the original string, for error positions
+        let mut start = s.len() - s.trim_start().len();
+
+        let s = s.trim();
+        let s = s.strip_suffix([';', ',']).unwrap_or(s).trim_end();
+        let s = match s.strip_prefix('#') {
+            Some(rest) => {
+                start += 1;
+                rest
+            }
+            None => s,
+        };
+
+        let bytes = s.as_bytes();
+        let digit = |i: usize| hex_digit(bytes[i]).ok_or(ParseColorError::InvalidDigit { position: start + i });
+
+        match bytes.len() {
+            3 | 4 => {
+                let r = digit(0)? * 17;
+                let g = digit(1)? * 17;
+                let b = digit(2)? * 17;
+                let a = if bytes.len() == 4 { digit(3)? * 17 } else { 255 };
+
+                Ok(EncodedColor::new(r, g, b, a))
+            }
+            6 | 8 => {
+                let pair = |i: usize| Ok::<_, ParseColorError>(digit(i)? << 4 | digit(i + 1)?);
+
+                let r = pair(0)?;
+                let g = pair(2)?;
+                let b = pair(4)?;
+                let a = if bytes.len() == 8 { pair(6)? } else { 255 };
+
+                Ok(EncodedColor::new(r, g, b, a))
+            }
+            _ => Err(ParseColorError::InvalidLength),
+        }
+    }
+}
+
+impl EncodedColor {
+    /// Parses a color from a list of integers, like `107,158,190,255` or `107 158 190`, which is
+    /// how a lot of tools export colors.
+    ///
+    /// The components can be separated by commas (with or without spaces) or just whitespace.
+    /// There must be 3 or 4 of them, each from 0 to 255, and alpha is 255 when it isn't given.
+    pub fn from_comma_separated(s: &str) -> Result<EncodedColor, ParseColorError> {
+        let mut components = [255; 4];
+        let mut count = 0;
+
+        let mut parse = |token: &str| {
+            // where this token starts in `s`, for error positions
+            let position = token.as_ptr() as usize - s.as_ptr() as usize;
+
+            let slot = components
+                .get_mut(count)
+                .ok_or(ParseColorError::InvalidComponentCount)?;
+            *slot = token
+                .parse()
+                .map_err(|_| ParseColorError::InvalidComponent { position })?;
+            count += 1;
+
+            Ok(())
+        };
+
+        let trimmed = s.trim();
+        if trimmed.contains(',') {
+            trimmed.split(',').map(str::trim).try_for_each(&mut parse)?;
+        } else {
+            trimmed.split_whitespace().try_for_each(&mut parse)?;
+        }
+
+        if count < 3 {
+            return Err(ParseColorError::InvalidComponentCount);
+        }
+
+        let [r, g, b, a] = components;
+        Ok(EncodedColor::new(r, g, b, a))
+    }
+}
+
+impl EncodedColor {
+    /// Parses a palette file with one hex color per line, like the `.hex` palettes from
+    /// Lospec. Each line is parsed like [FromStr], so `#6b9ebe`, `6b9ebe`, and `#6b9ebeff`
+    /// all work.
+    ///
+    /// Blank lines are skipped, and so are comments: lines starting with a `#` and then
+    /// whitespace (or nothing). So `# warm tones` is a comment, but `#fa0` is a color.
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// let palette = EncodedColor::parse_palette("# sunset\n#ff8000\n\n#6b9ebe\n").unwrap();
+    /// assert_eq!(palette, [EncodedColor::new(255, 128, 0, 255), EncodedColor::new(107, 158, 190, 255)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn parse_palette(text: &str) -> Result<Vec<EncodedColor>, ParsePaletteError> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let line = line.trim();
+                let is_comment = line
+                    .strip_prefix('#')
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace));
+
+                !line.is_empty() && !is_comment
+            })
+            .map(|(i, line)| line.parse().map_err(|error| ParsePaletteError { line: i + 1, error }))
+            .collect()
+    }
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex() {
+        let cornwall_blue = EncodedColor::new(107, 158, 190, 255);
+
+        assert_eq!("#6b9ebe".parse(), Ok(cornwall_blue));
+        assert_eq!("6b9ebe".parse(), Ok(cornwall_blue));
+        assert_eq!("#6B9EBE".parse(), Ok(cornwall_blue));
+        assert_eq!("#6b9ebeff".parse(), Ok(cornwall_blue));
+        assert_eq!("#6b9ebe80".parse(), Ok(cornwall_blue.with_a(128)));
+        assert_eq!("#fff".parse(), Ok(EncodedColor::WHITE));
+        assert_eq!("#0000".parse(), Ok(EncodedColor::CLEAR));
+
+        // stuff people copy out of chat apps and css...
+        assert_eq!("  #ABCDEF \n".parse(), Ok(EncodedColor::new(0xab, 0xcd, 0xef, 255)));
+        assert_eq!("\t#6b9ebe;".parse(), Ok(cornwall_blue));
+        assert_eq!("#6b9ebe, ".parse(), Ok(cornwall_blue));
+        assert_eq!("#6b9ebe ;".parse(), Ok(cornwall_blue));
+
+        // ...but we don't go too far.
+        let err = |s: &str| s.parse::<EncodedColor>().unwrap_err();
+        assert_eq!(err(""), ParseColorError::InvalidLength);
+        assert_eq!(err("#"), ParseColorError::InvalidLength);
+        assert_eq!(err("#6b9eb"), ParseColorError::InvalidLength);
+        assert_eq!(err("#6b9ebe;;"), ParseColorError::InvalidLength);
+        assert_eq!(err("##6b9eb"), ParseColorError::InvalidDigit { position: 1 });
+        assert_eq!(err("#6b 9eb"), ParseColorError::InvalidDigit { position: 3 });
+        assert_eq!(err("#6b9ebg"), ParseColorError::InvalidDigit { position: 6 });
+        assert_eq!(err("#+b9ebe"), ParseColorError::InvalidDigit { position: 1 });
+    }
+
+    #[test]
+    fn error_position() {
+        let err = |s: &str| s.parse::<EncodedColor>().unwrap_err();
+
+        // the first bad character, counting from the start of the whole string
+        assert_eq!(err("#12zz56").position(), Some(3));
+        assert_eq!(err("12zz56").position(), Some(2));
+        assert_eq!(err("  #12zz56;").position(), Some(5));
+        assert_eq!(err("\t1g3").position(), Some(2));
+        assert_eq!(err("#12").position(), None);
+    }
+
+    #[test]
+    fn from_comma_separated() {
+        let cornwall_blue = EncodedColor::new(107, 158, 190, 255);
+
+        assert_eq!(EncodedColor::from_comma_separated("107,158,190"), Ok(cornwall_blue));
+        assert_eq!(
+            EncodedColor::from_comma_separated("107, 158, 190, 255"),
+            Ok(cornwall_blue)
+        );
+        assert_eq!(EncodedColor::from_comma_separated(" 107 158\t190 "), Ok(cornwall_blue));
+        assert_eq!(
+            EncodedColor::from_comma_separated("107,158,190,128"),
+            Ok(cornwall_blue.with_a(128))
+        );
+        assert_eq!(EncodedColor::from_comma_separated("0 0 0 0"), Ok(EncodedColor::CLEAR));
+
+        let err = |s: &str| EncodedColor::from_comma_separated(s).unwrap_err();
+        assert_eq!(err("107,300,190"), ParseColorError::InvalidComponent { position: 4 });
+        assert_eq!(
+            err("107, 158, 19.5"),
+            ParseColorError::InvalidComponent { position: 10 }
+        );
+        assert_eq!(err("107,-1,190"), ParseColorError::InvalidComponent { position: 4 });
+        assert_eq!(err("107,,190"), ParseColorError::InvalidComponent { position: 4 });
+        assert_eq!(err("107 red 190"), ParseColorError::InvalidComponent { position: 4 });
+        assert_eq!(err("107,158"), ParseColorError::InvalidComponentCount);
+        assert_eq!(err("1,2,3,4,5"), ParseColorError::InvalidComponentCount);
+        assert_eq!(err(""), ParseColorError::InvalidComponentCount);
+        assert_eq!(err("107,300,190").position(), Some(4));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_palette() {
+        let text = "# a small palette\n#\n#1a1c2c\n  5d275d  \n\n#b13e53ff\n\t# the end\r\n#fa0\r\n";
+        assert_eq!(
+            EncodedColor::parse_palette(text),
+            Ok(alloc::vec![
+                EncodedColor::new(0x1a, 0x1c, 0x2c, 255),
+                EncodedColor::new(0x5d, 0x27, 0x5d, 255),
+                EncodedColor::new(0xb1, 0x3e, 0x53, 255),
+                EncodedColor::new(0xff, 0xaa, 0x00, 255),
+            ])
+        );
+        assert_eq!(EncodedColor::parse_palette(""), Ok(alloc::vec![]));
+
+        // errors point at the line, and the position in it
+        let err = EncodedColor::parse_palette("#1a1c2c\n# comment\n#12zz56\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParsePaletteError {
+                line: 3,
+                error: ParseColorError::InvalidDigit { position: 3 }
+            }
+        );
+        assert_eq!(
+            EncodedColor::parse_palette("#comment").unwrap_err().error,
+            ParseColorError::InvalidLength
+        );
+    }
+}
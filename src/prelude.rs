@@ -0,0 +1,25 @@
+//! The types and functions most uses of this crate need, for glob importing.
+//!
+//! ```
+//! use smol_rgb::prelude::*;
+//!
+//! let sky: EncodedColor = "#6b9ebe".parse().unwrap();
+//! let linear: LinearColor = sky.to_linear();
+//! assert_eq!(linear.to_encoded_space(), sky);
+//! assert_eq!(linear_to_encoded(encoded_to_linear(107)), 107);
+//! ```
+//!
+//! This has every color type, the types you pass to their methods to pick how they work (like
+//! [HuePath] and [Tonemap]), palettes and LUTs, and the error types. Bigger tools, like gradients
+//! and [HueCycler](crate::HueCycler), stay at the crate root. The only trait is [ConvertColor],
+//! for implementing conversions to your own color spaces. Items which need a feature, like
+//! `Lut3d`, are only here when that feature is on.
+
+pub use crate::{
+    AlphaMode, BlendFactor, BlendMode, BlendOp, ChannelWeights, ColorBlindness, ConvertColor, EncodedColor, EncodedRgb,
+    GamutMapping, Hsl, Hsv, HuePath, Lab, LinearColor, LinearColorBits, Oklab, Oklch, Palette, PalettedColor,
+    ParseColorError, Tonemap, Xyz, encoded_to_linear, linear_to_encoded,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::{Lut3d, ParseCubeError, ParsePaletteError};
@@ -0,0 +1,129 @@
+use crate::{EncodedColor, LinearColor};
+
+impl EncodedColor {
+    /// Converts this color from straight alpha into associated (premultiplied) alpha, where the
+    /// color channels have already been multiplied by alpha. The multiply happens in linear
+    /// space, and the result is encoded again.
+    ///
+    /// Storing premultiplied colors in 8 bits loses precision, and the lower the alpha, the
+    /// more is lost: at an alpha of 2, there are only a couple of distinct steps left in each
+    /// channel. Converting back with [from_premultiplied](Self::from_premultiplied) can't
+    /// bring those back.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn to_premultiplied(self) -> EncodedColor {
+        let linear = self.to_linear();
+        let a = linear.a;
+
+        LinearColor::new(linear.r * a, linear.g * a, linear.b * a, a)
+            .to_encoded_space()
+            .with_a(self.a)
+    }
+
+    /// Converts this color from associated (premultiplied) alpha, as some file formats store
+    /// it, into straight alpha. The divide happens in linear space, and the result is encoded
+    /// again. Fully transparent colors have no color left to recover, so they become
+    /// [CLEAR](Self::CLEAR).
+    ///
+    /// At low alpha, there's very little precision left in a premultiplied color, so the result
+    /// can be far from the original. See [to_premultiplied](Self::to_premultiplied).
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn from_premultiplied(self) -> EncodedColor {
+        if self.a == 0 {
+            return EncodedColor::CLEAR;
+        }
+
+        let linear = self.to_linear();
+        let a = linear.a;
+
+        // a premultiplied channel can't be above alpha, but bad data can be, so we clamp
+        LinearColor::new(
+            (linear.r / a).min(1.0),
+            (linear.g / a).min(1.0),
+            (linear.b / a).min(1.0),
+            a,
+        )
+        .to_encoded_space()
+        .with_a(self.a)
+    }
+
+    /// Converts a whole buffer from associated (premultiplied) alpha into straight alpha, in
+    /// place, for the load path of images which are stored premultiplied. Each pixel gets the
+    /// same result as [from_premultiplied](Self::from_premultiplied).
+    ///
+    /// Opaque pixels are the same either way, so they're skipped, which makes this cheap on
+    /// mostly opaque images.
+    pub fn assume_premultiplied(pixels: &mut [EncodedColor]) {
+        for pixel in pixels {
+            if pixel.a != 255 {
+                *pixel = pixel.from_premultiplied();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn premultiplied() {
+        // half transparent red, premultiplied in linear space
+        let premultiplied = EncodedColor::new(188, 0, 0, 128);
+        assert_eq!(EncodedColor::new(255, 0, 0, 128).to_premultiplied(), premultiplied);
+        assert_eq!(premultiplied.from_premultiplied(), EncodedColor::new(255, 0, 0, 128));
+
+        // opaque colors don't change
+        let cornwall_blue = EncodedColor::new(107, 158, 190, 255);
+        assert_eq!(cornwall_blue.to_premultiplied(), cornwall_blue);
+        assert_eq!(cornwall_blue.from_premultiplied(), cornwall_blue);
+
+        // at a reasonable alpha, we get back about what we started with
+        let faded = cornwall_blue.with_a(200);
+        let round_trip = faded.to_premultiplied().from_premultiplied();
+        for (a, b) in [
+            (faded.r, round_trip.r),
+            (faded.g, round_trip.g),
+            (faded.b, round_trip.b),
+        ] {
+            assert!(a.abs_diff(b) <= 1, "{:?}", round_trip);
+        }
+        assert_eq!(round_trip.a, 200);
+
+        // and transparent is transparent
+        assert_eq!(
+            EncodedColor::new(10, 20, 30, 0).from_premultiplied(),
+            EncodedColor::CLEAR
+        );
+        assert_eq!(EncodedColor::new(10, 20, 30, 0).to_premultiplied(), EncodedColor::CLEAR);
+        assert_eq!(
+            EncodedColor::new(255, 255, 255, 1).from_premultiplied(),
+            EncodedColor::WHITE.with_a(1)
+        );
+    }
+
+    #[test]
+    fn assume_premultiplied() {
+        let original: [EncodedColor; 6] = [
+            EncodedColor::new(255, 0, 0, 128),
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(107, 158, 190, 200),
+            EncodedColor::new(40, 220, 90, 30),
+            EncodedColor::new(10, 20, 30, 0),
+            EncodedColor::new(255, 255, 255, 1),
+        ];
+        let mut pixels = original.map(EncodedColor::to_premultiplied);
+        let premultiplied = pixels;
+
+        EncodedColor::assume_premultiplied(&mut pixels);
+        for (pixel, premultiplied) in pixels.iter().zip(premultiplied) {
+            assert_eq!(*pixel, premultiplied.from_premultiplied());
+        }
+
+        assert_eq!(pixels[0], original[0]);
+        assert_eq!(pixels[1], original[1]);
+        assert_eq!(pixels[4], EncodedColor::CLEAR);
+        for (pixel, original) in pixels.iter().zip(original) {
+            assert_eq!(pixel.a, original.a);
+        }
+    }
+}
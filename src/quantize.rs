@@ -0,0 +1,142 @@
+#[cfg(feature = "alloc")]
+use crate::{EncodedColor, LinearColor, Oklab};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+impl EncodedColor {
+    /// Picks up to `n` colors which represent `pixels` well, using median cut. This is the first
+    /// step of converting an image to indexed color, like for a GIF.
+    ///
+    /// The pixels are split up in [Oklab], so the palette spends its entries where people can
+    /// see the differences. Each palette entry is the average of its pixels, taken in linear
+    /// space (including alpha). Alpha isn't used to split the pixels up.
+    ///
+    /// You get fewer than `n` colors back if the image doesn't have that many distinct colors,
+    /// and none at all if `pixels` is empty.
+    pub fn median_cut_palette(pixels: &[EncodedColor], n: usize) -> Vec<EncodedColor> {
+        if pixels.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        let samples: Vec<(Oklab, LinearColor)> = pixels
+            .iter()
+            .map(|p| {
+                let linear = p.to_linear();
+                (linear.to_oklab(), linear)
+            })
+            .collect();
+
+        let mut boxes = Vec::with_capacity(n);
+        boxes.push(samples);
+
+        while boxes.len() < n {
+            // split the box with the biggest spread along any axis
+            let widest = boxes
+                .iter()
+                .enumerate()
+                .map(|(i, b)| (i, widest_axis(b)))
+                .filter(|(_, (_, range))| *range > 0.0)
+                .max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b));
+
+            let Some((index, (axis, _))) = widest else {
+                break;
+            };
+
+            let mut cut = boxes.swap_remove(index);
+            cut.sort_unstable_by(|a, b| axis_value(a.0, axis).total_cmp(&axis_value(b.0, axis)));
+
+            // cut at the median, but never between two equal values, which would leave the
+            // same color in both halves. Since the range is positive, there's a boundary somewhere.
+            let value = |i: usize| axis_value(cut[i].0, axis);
+            let boundaries = (1..cut.len()).filter(|&i| value(i - 1) != value(i));
+            let median = cut.len() / 2;
+            let split = boundaries.min_by_key(|&i| i.abs_diff(median)).unwrap();
+
+            let upper = cut.split_off(split);
+            boxes.push(cut);
+            boxes.push(upper);
+        }
+
+        boxes
+            .iter()
+            .map(|b| {
+                let mut sum = [0.0; 4];
+                for (_, linear) in b {
+                    sum[0] += linear.r;
+                    sum[1] += linear.g;
+                    sum[2] += linear.b;
+                    sum[3] += linear.a;
+                }
+
+                let count = b.len() as f32;
+                LinearColor::new(sum[0] / count, sum[1] / count, sum[2] / count, sum[3] / count).to_encoded_space()
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn axis_value(lab: Oklab, axis: usize) -> f32 {
+    match axis {
+        0 => lab.l,
+        1 => lab.a,
+        _ => lab.b,
+    }
+}
+
+/// Returns the axis with the largest range in this box, and that range.
+#[cfg(feature = "alloc")]
+fn widest_axis(samples: &[(Oklab, LinearColor)]) -> (usize, f32) {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+
+    for (lab, _) in samples {
+        for axis in 0..3 {
+            let v = axis_value(*lab, axis);
+            min[axis] = min[axis].min(v);
+            max[axis] = max[axis].max(v);
+        }
+    }
+
+    (0..3)
+        .map(|axis| (axis, max[axis] - min[axis]))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn median_cut() {
+        let teal = EncodedColor::TEAL;
+        let fuchsia = EncodedColor::FUCHSIA;
+
+        let mut image = Vec::new();
+        for i in 0..64 {
+            image.push(if i % 3 == 0 { teal } else { fuchsia });
+        }
+
+        // a two color image only has two colors to give
+        let mut palette = EncodedColor::median_cut_palette(&image, 4);
+        palette.sort();
+        let mut expected = [teal, fuchsia];
+        expected.sort();
+        assert_eq!(palette, expected);
+
+        assert_eq!(EncodedColor::median_cut_palette(&image, 1).len(), 1);
+        assert!(EncodedColor::median_cut_palette(&image, 0).is_empty());
+        assert!(EncodedColor::median_cut_palette(&[], 4).is_empty());
+
+        // a gradient is spread out across the palette
+        let ramp: Vec<_> = (0..=255).map(|v| EncodedColor::new(v, v, v, 255)).collect();
+        let mut palette = EncodedColor::median_cut_palette(&ramp, 4);
+        palette.sort();
+        assert_eq!(palette.len(), 4);
+        assert!(palette[0].r < 64 && palette[3].r > 192);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{Channel, EncodedColor, LinearColor, encoded_to_linear};
+use core::fmt;
+
+/// An [EncodedColor] without alpha, for when every pixel is opaque. This is 3 bytes rather
+/// than 4, so tightly packed buffers of these are a quarter smaller, which adds up for big
+/// images and on embedded targets.
+///
+/// Like [EncodedColor], this is in the encoded sRGB space, so convert it to linear with
+/// [to_linear](Self::to_linear) before blending.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(C)]
+pub struct EncodedRgb {
+    /// The red component of the color.
+    pub r: u8,
+
+    /// The green component of the color.
+    pub g: u8,
+
+    /// The blue component of the color.
+    pub b: u8,
+}
+
+impl EncodedRgb {
+    /// The number of channels in this color, for generic pixel buffer code.
+    pub const CHANNELS: usize = 3;
+
+    /// The order of the channels in memory, one byte each. This is `[R, G, B]`, which is the
+    /// same on every platform.
+    pub const LAYOUT: [Channel; 3] = [Channel::R, Channel::G, Channel::B];
+
+    /// Creates a new encoded 24bit color.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Adds an alpha channel, making an [EncodedColor].
+    pub const fn with_a(self, a: u8) -> EncodedColor {
+        EncodedColor::new(self.r, self.g, self.b, a)
+    }
+
+    /// Transforms this color into the Linear color space, with an alpha of 1.0.
+    #[inline]
+    pub fn to_linear(self) -> LinearColor {
+        LinearColor {
+            r: encoded_to_linear(self.r),
+            g: encoded_to_linear(self.g),
+            b: encoded_to_linear(self.b),
+            a: 1.0,
+        }
+    }
+}
+
+/// Adds an alpha of 255.
+impl From<EncodedRgb> for EncodedColor {
+    fn from(o: EncodedRgb) -> Self {
+        o.with_a(255)
+    }
+}
+
+/// Drops the alpha.
+impl From<EncodedColor> for EncodedRgb {
+    fn from(o: EncodedColor) -> Self {
+        Self::new(o.r, o.g, o.b)
+    }
+}
+
+impl From<EncodedRgb> for [u8; 3] {
+    fn from(o: EncodedRgb) -> Self {
+        [o.r, o.g, o.b]
+    }
+}
+
+impl From<[u8; 3]> for EncodedRgb {
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        Self { r, g, b }
+    }
+}
+
+impl fmt::Debug for EncodedRgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EncodedRgb")
+            .field(&self.r)
+            .field(&self.g)
+            .field(&self.b)
+            .finish()
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for EncodedRgb {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for EncodedRgb {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static_assertions::assert_eq_align!(EncodedRgb, u8);
+    static_assertions::assert_eq_size!(EncodedRgb, [u8; 3]);
+    static_assertions::assert_eq_size!([EncodedRgb; 4], [EncodedColor; 3]);
+
+    #[test]
+    fn round_trip() {
+        let sky = EncodedColor::new(107, 158, 190, 255);
+        let rgb = EncodedRgb::from(sky);
+        assert_eq!(rgb, EncodedRgb::new(107, 158, 190));
+        assert_eq!(EncodedColor::from(rgb), sky);
+        assert_eq!(EncodedRgb::from(sky.with_a(3)), rgb);
+        assert_eq!(rgb.with_a(3), sky.with_a(3));
+
+        assert_eq!(rgb.to_linear(), sky.to_linear());
+        assert_eq!(<[u8; 3]>::from(rgb), [107, 158, 190]);
+        assert_eq!(EncodedRgb::from([107, 158, 190]), rgb);
+        assert_eq!(EncodedRgb::default(), EncodedColor::BLACK.into());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck() {
+        let pixels = [EncodedRgb::new(1, 2, 3), EncodedRgb::new(4, 5, 6)];
+        let bytes: &[u8] = bytemuck::cast_slice(&pixels);
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,108 @@
+//! A serde representation for [LinearColor] as the `#rrggbbaa` hex string of its encoded
+//! color, for use with `#[serde(with = "...")]`.
+//!
+//! This is meant for config files, where people want to read and edit colors as hex, but the
+//! program wants them in linear space:
+//!
+//! ```
+//! # use smol_rgb::{EncodedColor, LinearColor};
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Light {
+//!     #[serde(with = "smol_rgb::serde_encoded_hex")]
+//!     color: LinearColor,
+//! }
+//!
+//! let light = Light { color: EncodedColor::new(107, 158, 190, 255).to_linear() };
+//! assert_eq!(serde_json::to_string(&light).unwrap(), r##"{"color":"#6b9ebeff"}"##);
+//! ```
+//!
+//! **This is lossy.** The color is encoded to 8 bits per channel on the way out, so it comes
+//! back as the nearest color an [EncodedColor] can hold, and anything outside of 0.0 to 1.0 is
+//! clamped. Colors which came from an [EncodedColor] in the first place round-trip exactly.
+//!
+//! Deserializing takes anything [EncodedColor]'s `FromStr` does, like `#6b9ebe` or `6B9EBE`.
+
+use crate::{EncodedColor, LinearColor};
+use core::fmt;
+use serde::{
+    Deserializer, Serializer,
+    de::{self, Visitor},
+};
+
+/// Serializes a [LinearColor] as the `#rrggbbaa` hex string of its encoded color.
+pub fn serialize<S>(color: &LinearColor, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut buf = [0; 9];
+    let encoded = color.to_encoded_space().with_a(color.alpha_u8());
+
+    serializer.serialize_str(encoded.write_hex(&mut buf))
+}
+
+/// Deserializes a [LinearColor] from a hex string, like `#6b9ebeff`, by parsing it as an
+/// [EncodedColor] and converting it to linear.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<LinearColor, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(HexVisitor)
+}
+
+struct HexVisitor;
+
+impl<'de> Visitor<'de> for HexVisitor {
+    type Value = LinearColor;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a hex color string, like `#6b9ebeff`")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse::<EncodedColor>()
+            .map(EncodedColor::to_linear)
+            .map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_hex() {
+        let color = LinearColor::new(0.3, 0.5, 0.8, 0.5);
+
+        let serialized = serialize(&color, serde_json::value::Serializer).unwrap();
+        assert_eq!(serialized, serde_json::json!("#95bce880"));
+
+        let deserialized = deserialize(serialized).unwrap();
+        for (a, b) in [
+            (color.r, deserialized.r),
+            (color.g, deserialized.g),
+            (color.b, deserialized.b),
+            (color.a, deserialized.a),
+        ] {
+            assert!((a - b).abs() < 0.01, "{:?} {:?}", color, deserialized);
+        }
+
+        // which is as close as it gets, so it's stable from then on
+        let again = serialize(&deserialized, serde_json::value::Serializer).unwrap();
+        assert_eq!(deserialize(again).unwrap(), deserialized);
+
+        // encoded colors round-trip exactly
+        let sky = EncodedColor::new(107, 158, 190, 77).to_linear();
+        let serialized = serialize(&sky, serde_json::value::Serializer).unwrap();
+        assert_eq!(deserialize(serialized).unwrap(), sky);
+
+        assert_eq!(
+            deserialize(serde_json::json!("FFF")).unwrap(),
+            EncodedColor::WHITE.to_linear()
+        );
+        assert!(deserialize(serde_json::json!("#6b9ebeg0")).is_err());
+        assert!(deserialize(serde_json::json!(12)).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! A serde representation for [EncodedColor] as a single packed `u32`, for use with
+//! `#[serde(with = "...")]`.
+//!
+//! The color is packed as `0xRRGGBBAA`, so red is the most significant byte and alpha the least.
+//! This is done with shifts, so the number is the same on every platform. Lots of APIs and
+//! databases store colors this way, and it's much smaller than the default tuple in JSON:
+//!
+//! ```
+//! # use smol_rgb::EncodedColor;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Tag {
+//!     #[serde(with = "smol_rgb::serde_packed_u32")]
+//!     color: EncodedColor,
+//! }
+//!
+//! let tag = Tag { color: EncodedColor::new(107, 158, 190, 255) };
+//! assert_eq!(serde_json::to_string(&tag).unwrap(), r#"{"color":1805565695}"#);
+//! ```
+
+use crate::EncodedColor;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes an [EncodedColor] as a `u32`, packed as `0xRRGGBBAA`.
+pub fn serialize<S>(color: &EncodedColor, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let packed = (color.r as u32) << 24 | (color.g as u32) << 16 | (color.b as u32) << 8 | color.a as u32;
+
+    packed.serialize(serializer)
+}
+
+/// Deserializes an [EncodedColor] from a `u32`, packed as `0xRRGGBBAA`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<EncodedColor, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let packed = u32::deserialize(deserializer)?;
+
+    Ok(EncodedColor {
+        r: (packed >> 24) as u8,
+        g: (packed >> 16) as u8,
+        b: (packed >> 8) as u8,
+        a: packed as u8,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_u32() {
+        let color = EncodedColor::new(107, 158, 190, 255);
+
+        let serialized = serialize(&color, serde_json::value::Serializer).unwrap();
+        assert_eq!(serialized, serde_json::json!(0x6b9ebeffu32));
+
+        let deserialized = deserialize(serialized).unwrap();
+        assert_eq!(deserialized, color);
+
+        let deserialized = deserialize(serde_json::json!(0x000000ffu32)).unwrap();
+        assert_eq!(deserialized, EncodedColor::BLACK);
+
+        // no negatives, and nothing bigger than a u32
+        assert!(deserialize(serde_json::json!(-1)).is_err());
+        assert!(deserialize(serde_json::json!(0x1_0000_0000u64)).is_err());
+        assert!(deserialize(serde_json::json!("#6b9ebeff")).is_err());
+    }
+}
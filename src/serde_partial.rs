@@ -0,0 +1,185 @@
+//! Partial deserialization for [EncodedColor], for layered config where an override only
+//! changes some channels.
+//!
+//! An [EncodedColorPatch] is a map with any subset of `r`, `g`, `b`, and `a`. The channels it
+//! leaves out come from a base color when you [apply](EncodedColorPatch::apply) it:
+//!
+//! ```
+//! # use smol_rgb::{EncodedColor, serde_partial::EncodedColorPatch};
+//! #[derive(serde::Deserialize)]
+//! struct ThemeOverride {
+//!     #[serde(default)]
+//!     accent: EncodedColorPatch,
+//! }
+//!
+//! let theme: ThemeOverride = serde_json::from_str(r#"{ "accent": { "a": 128 } }"#).unwrap();
+//! assert_eq!(theme.accent.apply(EncodedColor::WHITE), EncodedColor::new(255, 255, 255, 128));
+//! ```
+//!
+//! If you already have the base color in hand, [OverBase] deserializes straight into an
+//! [EncodedColor] instead.
+
+use crate::{EncodedColor, serde_struct::Field};
+use core::fmt;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, DeserializeSeed, MapAccess, Visitor},
+    ser::SerializeStruct,
+};
+
+/// Some, all, or none of the channels of an [EncodedColor]. Deserializes from a map with any
+/// subset of `r`, `g`, `b`, and `a`, and serializes only the channels which are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EncodedColorPatch {
+    /// The red component, if it's overridden.
+    pub r: Option<u8>,
+
+    /// The green component, if it's overridden.
+    pub g: Option<u8>,
+
+    /// The blue component, if it's overridden.
+    pub b: Option<u8>,
+
+    /// The alpha component, if it's overridden.
+    pub a: Option<u8>,
+}
+
+impl EncodedColorPatch {
+    /// Creates a color out of `base`, with any channels set in this patch overridden.
+    pub fn apply(self, base: EncodedColor) -> EncodedColor {
+        EncodedColor {
+            r: self.r.unwrap_or(base.r),
+            g: self.g.unwrap_or(base.g),
+            b: self.b.unwrap_or(base.b),
+            a: self.a.unwrap_or(base.a),
+        }
+    }
+}
+
+impl From<EncodedColor> for EncodedColorPatch {
+    fn from(o: EncodedColor) -> Self {
+        Self {
+            r: Some(o.r),
+            g: Some(o.g),
+            b: Some(o.b),
+            a: Some(o.a),
+        }
+    }
+}
+
+impl Serialize for EncodedColorPatch {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let channels = [("r", self.r), ("g", self.g), ("b", self.b), ("a", self.a)];
+        let len = channels.iter().filter(|(_, c)| c.is_some()).count();
+
+        let mut s = serializer.serialize_struct("EncodedColorPatch", len)?;
+        for (name, channel) in channels {
+            match channel {
+                Some(channel) => s.serialize_field(name, &channel)?,
+                None => s.skip_field(name)?,
+            }
+        }
+
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedColorPatch {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(PatchVisitor)
+    }
+}
+
+struct PatchVisitor;
+
+impl<'de> Visitor<'de> for PatchVisitor {
+    type Value = EncodedColorPatch;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map with any of the u8 fields `r`, `g`, `b`, and `a`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut patch = EncodedColorPatch::default();
+
+        while let Some(key) = map.next_key()? {
+            let (slot, name) = match key {
+                Field::R => (&mut patch.r, "r"),
+                Field::G => (&mut patch.g, "g"),
+                Field::B => (&mut patch.b, "b"),
+                Field::A => (&mut patch.a, "a"),
+            };
+
+            if slot.is_some() {
+                return Err(de::Error::duplicate_field(name));
+            }
+            *slot = Some(map.next_value()?);
+        }
+
+        Ok(patch)
+    }
+}
+
+/// A [DeserializeSeed] which deserializes an [EncodedColorPatch] and applies it over the
+/// wrapped base color, giving back a whole [EncodedColor].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OverBase(pub EncodedColor);
+
+impl<'de> DeserializeSeed<'de> for OverBase {
+    type Value = EncodedColor;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        EncodedColorPatch::deserialize(deserializer).map(|patch| patch.apply(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial() {
+        let patch: EncodedColorPatch = serde_json::from_str(r#"{ "r": 10 }"#).unwrap();
+        assert_eq!(patch.apply(EncodedColor::WHITE), EncodedColor::new(10, 255, 255, 255));
+
+        let mut de = serde_json::Deserializer::from_str(r#"{ "r": 10, "a": 0 }"#);
+        let color = OverBase(EncodedColor::WHITE).deserialize(&mut de).unwrap();
+        assert_eq!(color, EncodedColor::new(10, 255, 255, 0));
+
+        let patch: EncodedColorPatch = serde_json::from_str("{}").unwrap();
+        assert_eq!(patch.apply(EncodedColor::TEAL), EncodedColor::TEAL);
+
+        let patch: EncodedColorPatch = serde_yaml::from_str("g: 3\nb: 4").unwrap();
+        assert_eq!(patch.apply(EncodedColor::BLACK), EncodedColor::new(0, 3, 4, 255));
+
+        // bad channels are still errors
+        assert!(serde_json::from_str::<EncodedColorPatch>(r#"{ "r": 256 }"#).is_err());
+        assert!(serde_json::from_str::<EncodedColorPatch>(r#"{ "red": 1 }"#).is_err());
+        assert!(serde_json::from_str::<EncodedColorPatch>(r#"{ "r": 1, "r": 2 }"#).is_err());
+        assert!(serde_json::from_str::<EncodedColorPatch>("[1, 2, 3, 4]").is_err());
+
+        // and only the set channels serialize
+        let patch = EncodedColorPatch {
+            g: Some(7),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(patch).unwrap();
+        assert_eq!(json, serde_json::json!({ "g": 7 }));
+        assert_eq!(serde_json::from_value::<EncodedColorPatch>(json).unwrap(), patch);
+
+        let full = EncodedColorPatch::from(EncodedColor::RED);
+        assert_eq!(full.apply(EncodedColor::CLEAR), EncodedColor::RED);
+    }
+}
@@ -0,0 +1,198 @@
+//! A struct-style serde representation for [EncodedColor], for use with `#[serde(with = "...")]`.
+//!
+//! By default, [EncodedColor] serializes as a tuple, like `[107, 158, 190, 255]`. That's compact,
+//! but in hand-written config files, a map with field names is easier to read, and errors can
+//! name the field which went wrong:
+//!
+//! ```
+//! # use smol_rgb::EncodedColor;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Theme {
+//!     #[serde(with = "smol_rgb::serde_struct")]
+//!     accent: EncodedColor,
+//! }
+//!
+//! let theme: Theme = serde_json::from_str(r#"{ "accent": { "r": 107, "g": 158, "b": 190, "a": 255 } }"#).unwrap();
+//! assert_eq!(theme.accent, EncodedColor::new(107, 158, 190, 255));
+//! ```
+//!
+//! All four fields are required, and unknown fields are rejected.
+
+use crate::EncodedColor;
+use core::fmt;
+use serde::{
+    Deserializer, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+};
+
+const FIELDS: &[&str] = &["r", "g", "b", "a"];
+
+/// Serializes an [EncodedColor] as a struct with `r`, `g`, `b`, and `a` fields.
+pub fn serialize<S>(color: &EncodedColor, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut s = serializer.serialize_struct(crate::ENCODED_NAME, 4)?;
+
+    s.serialize_field("r", &color.r)?;
+    s.serialize_field("g", &color.g)?;
+    s.serialize_field("b", &color.b)?;
+    s.serialize_field("a", &color.a)?;
+
+    s.end()
+}
+
+/// Deserializes an [EncodedColor] from a struct with `r`, `g`, `b`, and `a` fields.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<EncodedColor, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_struct(crate::ENCODED_NAME, FIELDS, StructVisitor)
+}
+
+pub(crate) enum Field {
+    R,
+    G,
+    B,
+    A,
+}
+
+impl<'de> serde::Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("`r`, `g`, `b`, or `a`")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "r" => Ok(Field::R),
+                    "g" => Ok(Field::G),
+                    "b" => Ok(Field::B),
+                    "a" => Ok(Field::A),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct StructVisitor;
+
+impl<'de> Visitor<'de> for StructVisitor {
+    type Value = EncodedColor;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a struct with u8 `r`, `g`, `b`, and `a` fields")
+    }
+
+    // non-self-describing formats, like bincode, hand us structs as sequences.
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let r = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let g = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let b = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let a = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(3, &self))?;
+
+        Ok(EncodedColor { r, g, b, a })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut r = None;
+        let mut g = None;
+        let mut b = None;
+        let mut a = None;
+
+        while let Some(key) = map.next_key()? {
+            let (slot, name) = match key {
+                Field::R => (&mut r, "r"),
+                Field::G => (&mut g, "g"),
+                Field::B => (&mut b, "b"),
+                Field::A => (&mut a, "a"),
+            };
+
+            if slot.is_some() {
+                return Err(de::Error::duplicate_field(name));
+            }
+            *slot = Some(map.next_value()?);
+        }
+
+        Ok(EncodedColor {
+            r: r.ok_or_else(|| de::Error::missing_field("r"))?,
+            g: g.ok_or_else(|| de::Error::missing_field("g"))?,
+            b: b.ok_or_else(|| de::Error::missing_field("b"))?,
+            a: a.ok_or_else(|| de::Error::missing_field("a"))?,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn from_json(json: &str) -> Result<EncodedColor, serde_json::Error> {
+        deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    #[test]
+    fn struct_form() {
+        use std::string::ToString;
+
+        let color = EncodedColor::new(107, 158, 190, 255);
+
+        let mut buf = std::vec::Vec::new();
+        serialize(&color, &mut serde_json::Serializer::new(&mut buf)).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&buf).unwrap(),
+            r#"{"r":107,"g":158,"b":190,"a":255}"#
+        );
+
+        assert_eq!(from_json(r#"{"r":107,"g":158,"b":190,"a":255}"#).unwrap(), color);
+        assert_eq!(
+            from_json(r#"{ "a": 255, "b": 190, "g": 158, "r": 107 }"#).unwrap(),
+            color
+        );
+
+        // errors name the field
+        let err = from_json(r#"{"r":107,"g":158,"b":190,"a":255,"alpha":3}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `alpha`"), "{}", err);
+        let err = from_json(r#"{"r":107,"g":158,"b":190}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `a`"), "{}", err);
+        let err = from_json(r#"{"r":107,"g":158,"b":190,"a":255,"r":1}"#).unwrap_err();
+        assert!(err.to_string().contains("duplicate field `r`"), "{}", err);
+        assert!(from_json(r#"{"r":107,"g":158,"b":300,"a":255}"#).is_err());
+
+        // and non-self-describing formats still work
+        let mut buf = std::vec::Vec::new();
+        serialize(
+            &color,
+            &mut bincode::Serializer::new(&mut buf, bincode::DefaultOptions::new()),
+        )
+        .unwrap();
+        assert_eq!(buf, [107, 158, 190, 255]);
+        let round_trip = deserialize(&mut bincode::Deserializer::from_slice(
+            &buf,
+            bincode::DefaultOptions::new(),
+        ))
+        .unwrap();
+        assert_eq!(color, round_trip);
+    }
+}
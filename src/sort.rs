@@ -0,0 +1,135 @@
+use crate::EncodedColor;
+use core::cmp::Ordering;
+
+impl EncodedColor {
+    /// Compares two colors by how light they look (their Oklab lightness), from darkest to
+    /// lightest. This is the order palette UIs usually want, unlike the derived [Ord], which
+    /// compares the raw bytes.
+    ///
+    /// Colors which are exactly as light as each other fall back to the derived [Ord], so this
+    /// is a total order and sorting with it always gives the same result. Alpha only counts in
+    /// that fallback.
+    pub fn cmp_by_lightness(self, other: EncodedColor) -> Ordering {
+        self.to_oklab()
+            .l
+            .total_cmp(&other.to_oklab().l)
+            .then_with(|| self.cmp(&other))
+    }
+
+    /// Compares two colors by their `#rrggbbaa` hex value, so sorting with this gives the same
+    /// order as sorting their hex strings. Use this when writing colors to disk in sorted maps
+    /// or sets.
+    ///
+    /// This is the same order as the derived [Ord], since the fields are declared in
+    /// `r, g, b, a` order, but it says what you mean when the hex order is what you rely on.
+    pub fn cmp_by_hex(self, other: EncodedColor) -> Ordering {
+        [self.r, self.g, self.b, self.a].cmp(&[other.r, other.g, other.b, other.a])
+    }
+
+    /// Sorts `colors` from darkest to lightest, using [cmp_by_lightness](Self::cmp_by_lightness).
+    pub fn sort_by_lightness(colors: &mut [EncodedColor]) {
+        colors.sort_unstable_by(|a, b| a.cmp_by_lightness(*b));
+    }
+
+    /// Sorts `colors` into the order you'd want for a palette of swatches: grouped by hue, and
+    /// from darkest to lightest within each group.
+    ///
+    /// The hue groups are 30 degree slices of the Oklch hue wheel, starting from pinks and reds
+    /// and going around through yellows, greens, and blues. Grays (and near-grays) don't really
+    /// have a hue, so they go in their own group, first.
+    pub fn palette_sort(colors: &mut [EncodedColor]) {
+        colors.sort_unstable_by(|a, b| hue_group(*a).cmp(&hue_group(*b)).then_with(|| a.cmp_by_lightness(*b)));
+    }
+}
+
+/// Which group a color goes in for [EncodedColor::palette_sort]. Grays are group 0.
+fn hue_group(color: EncodedColor) -> u8 {
+    // below this Oklch chroma, colors look gray enough that their hue is just noise
+    const GRAY_CHROMA: f32 = 0.02;
+
+    let lch = color.to_oklch();
+    if lch.c < GRAY_CHROMA {
+        0
+    } else {
+        // offset by half a slice, so that the reds (around 30 degrees) don't straddle two slices
+        1 + ((lch.h + 15.0) / 30.0) as u8 % 12
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_by_lightness() {
+        let ramp: [EncodedColor; 8] = core::array::from_fn(|i| {
+            let v = (i * 36) as u8;
+            EncodedColor::new(v, v, v, 255)
+        });
+
+        let mut shuffled = [ramp[5], ramp[0], ramp[7], ramp[2], ramp[6], ramp[1], ramp[4], ramp[3]];
+        EncodedColor::sort_by_lightness(&mut shuffled);
+        assert_eq!(shuffled, ramp);
+
+        // it's perceptual, not by the bytes: a dim green is darker than pure blue
+        let blue = EncodedColor::BLUE;
+        let green = EncodedColor::new(0, 90, 0, 255);
+        assert_eq!(green.cmp_by_lightness(blue), Ordering::Less);
+        assert_eq!(green.cmp(&blue), Ordering::Greater);
+
+        // ties fall back to the bytes
+        assert_eq!(blue.with_a(0).cmp_by_lightness(blue), Ordering::Less);
+        assert_eq!(blue.cmp_by_lightness(blue), Ordering::Equal);
+    }
+
+    #[test]
+    fn cmp_by_hex() {
+        let mut colors = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(107, 158, 190, 0),
+            EncodedColor::new(0, 255, 255, 255),
+            EncodedColor::new(255, 0, 0, 0),
+            EncodedColor::new(1, 0, 0, 0),
+            EncodedColor::new(0, 0, 0, 255),
+            EncodedColor::CLEAR,
+            EncodedColor::WHITE,
+        ];
+        colors.sort_unstable_by(|a, b| a.cmp_by_hex(*b));
+
+        for pair in colors.windows(2) {
+            let (mut a, mut b) = ([0; 9], [0; 9]);
+            assert!(pair[0].write_hex(&mut a) < pair[1].write_hex(&mut b));
+
+            assert_eq!(pair[0].cmp_by_hex(pair[1]), pair[0].cmp(&pair[1]));
+        }
+        assert_eq!(colors[0], EncodedColor::CLEAR);
+        assert_eq!(colors[7], EncodedColor::WHITE);
+        assert_eq!(EncodedColor::RED.cmp_by_hex(EncodedColor::RED), Ordering::Equal);
+    }
+
+    #[test]
+    fn palette_sort() {
+        let light_red = EncodedColor::new(255, 150, 150, 255);
+        let red = EncodedColor::new(200, 30, 30, 255);
+        let dark_red = EncodedColor::new(90, 10, 10, 255);
+        let light_blue = EncodedColor::new(150, 180, 255, 255);
+        let blue = EncodedColor::new(30, 60, 200, 255);
+        let green = EncodedColor::new(40, 170, 60, 255);
+        let dark_green = EncodedColor::new(10, 70, 20, 255);
+        let gray = EncodedColor::new(128, 128, 128, 255);
+        let white = EncodedColor::WHITE;
+        let black = EncodedColor::BLACK;
+
+        let mut palette = [
+            blue, white, red, dark_green, light_red, black, green, light_blue, dark_red, gray,
+        ];
+        EncodedColor::palette_sort(&mut palette);
+
+        assert_eq!(
+            palette,
+            [
+                black, gray, white, dark_red, red, light_red, dark_green, green, blue, light_blue
+            ]
+        );
+    }
+}
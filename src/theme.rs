@@ -0,0 +1,353 @@
+use crate::{EncodedColor, LinearColor, Oklch};
+use core::hash::{Hash, Hasher};
+
+/// The Oklch lightness of each stop in [tonal_palette](EncodedColor::tonal_palette), in order
+/// from the `50` tone to the `900` tone.
+pub const TONAL_PALETTE_LIGHTNESS: [f32; 10] = [0.97, 0.93, 0.88, 0.81, 0.72, 0.64, 0.56, 0.48, 0.41, 0.34];
+
+impl EncodedColor {
+    /// Creates a tonal scale from this color, like the `50, 100, 200, ... 900` scales in
+    /// Material Design or Tailwind.
+    ///
+    /// Each tone keeps this color's Oklch hue and chroma, but has its lightness set to the
+    /// matching stop in [TONAL_PALETTE_LIGHTNESS]. Index 0 is the `50` tone (the lightest), and
+    /// index 9 is the `900` tone (the darkest). Alpha is kept.
+    ///
+    /// Colorful seeds can't keep all of their chroma at the ends of the scale, where they would
+    /// fall outside of sRGB. Those tones lose chroma, with
+    /// [gamut_clip_oklch](LinearColor::gamut_clip_oklch), instead.
+    pub fn tonal_palette(self) -> [EncodedColor; 10] {
+        let lch = self.to_oklch();
+        let alpha = self.alpha_f32();
+
+        TONAL_PALETTE_LIGHTNESS.map(|l| {
+            LinearColor::from_oklch(Oklch { l, ..lch }, alpha)
+                .gamut_clip_oklch()
+                .to_encoded_space()
+                .with_a(self.a)
+        })
+    }
+
+    /// Makes a readable `(background, foreground)` pair out of this color, like a button in a
+    /// brand color with legible text on it. The pair always passes
+    /// [passes_wcag_aa](Self::passes_wcag_aa).
+    ///
+    /// The background is this color, made opaque. The foreground keeps its Oklch hue and chroma,
+    /// but is made darker or lighter (whichever has more room) until the contrast is enough. We
+    /// stop as soon as it is, so the foreground stays as close to the seed as it can.
+    pub fn theme_pair(self) -> (EncodedColor, EncodedColor) {
+        let background = self.with_a(255);
+
+        // black or white always passes against any background, so we start with whichever has
+        // more contrast, and search between it and the seed for the passing lightness closest
+        // to the seed.
+        let target = if EncodedColor::BLACK.contrast_ratio(background) > EncodedColor::WHITE.contrast_ratio(background)
+        {
+            0.0
+        } else {
+            1.0
+        };
+        let foreground = background
+            .search_lightness(background, target, 4.5)
+            .expect("black or white always passes");
+
+        (background, foreground)
+    }
+
+    /// Makes this color darker or lighter, just enough that its
+    /// [contrast_ratio](Self::contrast_ratio) against `against` is at least `min_ratio`, for
+    /// automatically fixing text which is hard to read. If it's already enough, this color is
+    /// returned as-is. Alpha is kept.
+    ///
+    /// Only the Oklch lightness changes, so the hue and chroma are kept as much as sRGB allows.
+    /// We move away from `against`: lighter colors get lighter and darker colors get darker. If
+    /// that can't reach `min_ratio`, we try the other way, and if neither can (like a ratio over
+    /// 21.0, which nothing reaches), we return black or white, whichever has more contrast.
+    ///
+    /// ```
+    /// # use smol_rgb::EncodedColor;
+    /// let background = EncodedColor::new(107, 158, 190, 255);
+    /// let text = EncodedColor::new(90, 130, 160, 255).ensure_contrast(background, 4.5);
+    /// assert!(text.contrast_ratio(background) >= 4.5);
+    /// ```
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn ensure_contrast(self, against: EncodedColor, min_ratio: f32) -> EncodedColor {
+        if self.contrast_ratio(against) >= min_ratio {
+            return self;
+        }
+
+        let (away, toward) = if self.luminance() >= against.luminance() {
+            (1.0, 0.0)
+        } else {
+            (0.0, 1.0)
+        };
+
+        self.search_lightness(against, away, min_ratio)
+            .or_else(|| self.search_lightness(against, toward, min_ratio))
+            .unwrap_or_else(|| {
+                if EncodedColor::BLACK.contrast_ratio(against) > EncodedColor::WHITE.contrast_ratio(against) {
+                    EncodedColor::BLACK
+                } else {
+                    EncodedColor::WHITE
+                }
+            })
+            .with_a(self.a)
+    }
+
+    /// Searches the Oklch lightnesses between this color's and `target` for the one closest
+    /// to this color's which has `min_ratio` contrast against `background`. If even `target`
+    /// doesn't, this returns `None`. The result is opaque.
+    fn search_lightness(self, background: EncodedColor, target: f32, min_ratio: f32) -> Option<EncodedColor> {
+        let lch = self.to_oklch();
+        let at = |l: f32| {
+            LinearColor::from_oklch(Oklch { l, ..lch }, 1.0)
+                .gamut_clip_oklch()
+                .to_encoded_space()
+        };
+
+        let mut found = at(target);
+        if found.contrast_ratio(background) < min_ratio {
+            return None;
+        }
+
+        let (mut near, mut far) = (lch.l, target);
+        for _ in 0..24 {
+            let mid = (near + far) / 2.0;
+            let candidate = at(mid);
+
+            if candidate.contrast_ratio(background) >= min_ratio {
+                found = candidate;
+                far = mid;
+            } else {
+                near = mid;
+            }
+        }
+
+        Some(found)
+    }
+
+    /// Picks a color for `value` from its hash, for color-coding things like usernames or tags.
+    /// The same value always gives the same color, on every run and on every platform.
+    ///
+    /// Only the hue comes from the hash. The lightness and chroma are fixed, so every color is
+    /// equally easy to read against the same background, and none are garish. Alpha is 255.
+    ///
+    /// Different values usually get different colors, but with only so many hues to go around,
+    /// some will look alike.
+    pub fn from_hashable(value: impl Hash) -> EncodedColor {
+        // FNV-1a, since the hashers in `std` are randomly seeded. The integer writes default to
+        // native endian bytes, so we pin them to little endian, and widen `usize`s to 64 bits.
+        struct Fnv1a(u64);
+
+        impl Hasher for Fnv1a {
+            fn write(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+                }
+            }
+
+            fn write_u16(&mut self, i: u16) {
+                self.write(&i.to_le_bytes());
+            }
+
+            fn write_u32(&mut self, i: u32) {
+                self.write(&i.to_le_bytes());
+            }
+
+            fn write_u64(&mut self, i: u64) {
+                self.write(&i.to_le_bytes());
+            }
+
+            fn write_u128(&mut self, i: u128) {
+                self.write(&i.to_le_bytes());
+            }
+
+            fn write_usize(&mut self, i: usize) {
+                self.write_u64(i as u64);
+            }
+
+            fn finish(&self) -> u64 {
+                self.0
+            }
+        }
+
+        let mut hasher = Fnv1a(0xcbf29ce484222325);
+        value.hash(&mut hasher);
+
+        // FNV doesn't mix its top bits well for short inputs, so we finish with splitmix64's mixer
+        let mut h = hasher.finish();
+        h = (h ^ (h >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94d049bb133111eb);
+        h ^= h >> 31;
+
+        let hue = (h >> 40) as f32 / (1u64 << 24) as f32 * 360.0;
+
+        LinearColor::from_oklch(Oklch::new(0.7, 0.13, hue), 1.0)
+            .gamut_clip_oklch()
+            .to_encoded_space()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tonal_palette() {
+        let seeds = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(30, 30, 30, 128),
+            EncodedColor::RED,
+            EncodedColor::YELLOW,
+            EncodedColor::BLUE,
+        ];
+
+        for seed in seeds {
+            let palette = seed.tonal_palette();
+
+            for pair in palette.windows(2) {
+                assert!(pair[0].to_oklab().l > pair[1].to_oklab().l, "{:?}", palette);
+            }
+            assert!(palette.iter().all(|c| c.a == seed.a));
+        }
+
+        // and they keep their hue all the way down
+        for seed in [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::RED,
+            EncodedColor::BLUE,
+        ] {
+            let hue = seed.to_oklch().h;
+            for tone in seed.tonal_palette() {
+                assert!((tone.to_oklch().h - hue).abs() < 5.0, "{:?}", tone);
+            }
+        }
+    }
+
+    #[test]
+    fn from_hashable() {
+        let alice = EncodedColor::from_hashable("alice");
+        assert_eq!(alice, EncodedColor::from_hashable("alice"));
+
+        // the same on every run
+        assert_eq!(alice, EncodedColor::new(183, 157, 43, 255));
+
+        let names = ["alice", "bob", "carol", "dave", "eve"];
+        let colors = names.map(EncodedColor::from_hashable);
+        for (i, a) in colors.iter().enumerate() {
+            for b in &colors[i + 1..] {
+                assert_ne!(a, b);
+            }
+
+            // they're all about as light as each other
+            assert!((a.to_oklab().l - 0.7).abs() < 0.01, "{:?}", a);
+        }
+
+        assert_eq!(EncodedColor::from_hashable(42u32), EncodedColor::from_hashable(42u32));
+
+        // integers hash the same on every platform, and usizes the same as u64s
+        assert_eq!(
+            EncodedColor::from_hashable(42u32),
+            EncodedColor::new(216, 123, 176, 255)
+        );
+        assert_eq!(EncodedColor::from_hashable(42usize), EncodedColor::from_hashable(42u64));
+        assert_eq!(
+            EncodedColor::from_hashable(-1i16),
+            EncodedColor::from_hashable(u16::MAX)
+        );
+    }
+
+    #[test]
+    fn theme_pair() {
+        let seeds = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(30, 30, 30, 128),
+            EncodedColor::new(118, 118, 118, 255),
+            EncodedColor::RED,
+            EncodedColor::YELLOW,
+            EncodedColor::BLUE,
+            EncodedColor::BLACK,
+            EncodedColor::WHITE,
+        ];
+
+        for seed in seeds {
+            let (background, foreground) = seed.theme_pair();
+            assert_eq!(background, seed.with_a(255));
+            assert_eq!(foreground.a, 255);
+            assert!(
+                foreground.passes_wcag_aa(background),
+                "{:?} {:?}",
+                background,
+                foreground
+            );
+        }
+
+        // colorful seeds keep their hue in the foreground
+        let (background, foreground) = EncodedColor::new(107, 158, 190, 255).theme_pair();
+        assert!(foreground.to_oklch().c > 0.02);
+        assert!((foreground.to_oklch().h - background.to_oklch().h).abs() < 10.0);
+    }
+
+    #[test]
+    fn ensure_contrast() {
+        let backgrounds = [
+            EncodedColor::new(107, 158, 190, 255),
+            EncodedColor::new(118, 118, 118, 255),
+            EncodedColor::WHITE,
+            EncodedColor::BLACK,
+            EncodedColor::YELLOW,
+        ];
+        let colors = [
+            EncodedColor::new(90, 130, 160, 200),
+            EncodedColor::new(200, 30, 30, 255),
+            EncodedColor::new(120, 120, 120, 255),
+            EncodedColor::new(250, 240, 10, 255),
+        ];
+
+        for background in backgrounds {
+            for color in colors {
+                for ratio in [3.0, 4.5, 7.0] {
+                    let fixed = color.ensure_contrast(background, ratio);
+                    assert_eq!(fixed.a, color.a);
+
+                    // against a mid gray, even black and white can't reach 7.0
+                    let best = EncodedColor::BLACK
+                        .contrast_ratio(background)
+                        .max(EncodedColor::WHITE.contrast_ratio(background));
+                    if best < ratio {
+                        assert_eq!(fixed.contrast_ratio(background), best);
+                        continue;
+                    }
+
+                    assert!(
+                        fixed.contrast_ratio(background) >= ratio,
+                        "{:?} {:?} {} {:?}",
+                        background,
+                        color,
+                        ratio,
+                        fixed
+                    );
+                }
+            }
+        }
+
+        // already readable colors are left alone
+        assert_eq!(
+            EncodedColor::BLACK.ensure_contrast(EncodedColor::WHITE, 4.5),
+            EncodedColor::BLACK
+        );
+
+        // a lighter color gets lighter, and keeps its hue
+        let background = EncodedColor::new(60, 60, 60, 255);
+        let color = EncodedColor::new(90, 130, 160, 255);
+        let fixed = color.ensure_contrast(background, 4.5);
+        assert!(fixed.to_oklch().l > color.to_oklch().l);
+        assert!((fixed.to_oklch().h - color.to_oklch().h).abs() < 5.0);
+
+        // and it's just enough, not all the way to white
+        assert!(fixed.contrast_ratio(background) < 4.6);
+
+        // when nothing can get there, we get the best there is
+        assert_eq!(color.ensure_contrast(background, 30.0), EncodedColor::WHITE);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{EncodedColor, LinearColor};
+
+/// A tone-mapping operator, for squeezing HDR linear colors (with channels above 1.0) into
+/// the 0.0 to 1.0 range an [EncodedColor] can hold.
+///
+/// Every operator treats each channel on its own, and negative channels are clamped to 0.0
+/// first. Alpha is never touched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Tonemap {
+    /// Clamps each channel to 1.0, so everything brighter than white is white. This leaves
+    /// SDR colors exactly as they were.
+    #[default]
+    Clamp,
+
+    /// `c / (1 + c)`, which never quite reaches 1.0, so highlights keep their detail. This
+    /// darkens the whole image, mid grays included, so it's usually paired with some exposure.
+    Reinhard,
+
+    /// Krzysztof Narkowicz's fit of the ACES filmic curve, which has the contrasty look of
+    /// film and of most games. It reaches 1.0 at a linear value of about 10.
+    Aces,
+}
+
+impl LinearColor {
+    /// Applies the `tonemap` operator to this color's red, green, and blue. Alpha is kept.
+    #[must_use = "method returns a new color and does not mutate the original value"]
+    pub fn tonemap(self, tonemap: Tonemap) -> LinearColor {
+        let map = |c: f32| {
+            let c = c.max(0.0);
+            match tonemap {
+                Tonemap::Clamp => c.min(1.0),
+                Tonemap::Reinhard => c / (1.0 + c),
+                Tonemap::Aces => ((c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14)).min(1.0),
+            }
+        };
+
+        LinearColor::new(map(self.r), map(self.g), map(self.b), self.a)
+    }
+
+    /// Exposes, tone-maps, and encodes every color in `src` into `dst`, for exporting HDR
+    /// renders to SDR images in one pass without any buffers in between.
+    ///
+    /// `exposure` is in stops, so each color's red, green, and blue are scaled by
+    /// `2^exposure` before `tonemap` is applied. The results are the same as
+    /// [tonemap](Self::tonemap) and then [to_encoded_space](Self::to_encoded_space) on each
+    /// scaled color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` aren't the same length.
+    pub fn slice_tonemap_to_encoded(src: &[LinearColor], dst: &mut [EncodedColor], exposure: f32, tonemap: Tonemap) {
+        assert_eq!(src.len(), dst.len(), "src and dst must be the same length");
+
+        let scale = crate::math::powf(2.0, exposure);
+        for (out, color) in dst.iter_mut().zip(src) {
+            *out = LinearColor::new(color.r * scale, color.g * scale, color.b * scale, color.a)
+                .tonemap(tonemap)
+                .to_encoded_space();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TONEMAPS: [Tonemap; 3] = [Tonemap::Clamp, Tonemap::Reinhard, Tonemap::Aces];
+
+    #[test]
+    fn tonemap() {
+        let hdr = LinearColor::new(4.0, 0.5, -0.2, 0.5);
+
+        assert_eq!(hdr.tonemap(Tonemap::Clamp), LinearColor::new(1.0, 0.5, 0.0, 0.5));
+        assert_eq!(
+            hdr.tonemap(Tonemap::Reinhard),
+            LinearColor::new(0.8, 1.0 / 3.0, 0.0, 0.5)
+        );
+
+        for tonemap in TONEMAPS {
+            let mapped = hdr.tonemap(tonemap);
+            assert!(
+                mapped.to_array().iter().all(|c| (0.0..=1.0).contains(c)),
+                "{:?}",
+                mapped
+            );
+
+            // brighter stays brighter
+            let ramp = [0.0, 0.1, 0.5, 1.0, 2.0, 8.0].map(|c| LinearColor::new(c, c, c, 1.0).tonemap(tonemap).r);
+            for pair in ramp.windows(2) {
+                assert!(pair[0] <= pair[1], "{:?} {:?}", tonemap, ramp);
+            }
+        }
+
+        assert_eq!(LinearColor::new(20.0, 0.0, 0.0, 1.0).tonemap(Tonemap::Aces).r, 1.0);
+    }
+
+    #[test]
+    fn slice_tonemap_to_encoded() {
+        let src: [LinearColor; 16] =
+            core::array::from_fn(|i| LinearColor::new(i as f32 * 0.3, 0.02 * i as f32, 1.0 - i as f32 * 0.2, 0.75));
+
+        for tonemap in TONEMAPS {
+            for exposure in [-1.0, 0.0, 1.5] {
+                let mut dst = [EncodedColor::CLEAR; 16];
+                LinearColor::slice_tonemap_to_encoded(&src, &mut dst, exposure, tonemap);
+
+                for (color, out) in src.iter().zip(dst) {
+                    let scale = crate::math::powf(2.0, exposure);
+                    let exposed = LinearColor::new(color.r * scale, color.g * scale, color.b * scale, color.a);
+                    assert_eq!(out, exposed.tonemap(tonemap).to_encoded_space());
+                }
+            }
+        }
+
+        // no exposure and clamping is just encoding
+        let mut dst = [EncodedColor::CLEAR; 2];
+        let sky = EncodedColor::new(107, 158, 190, 255);
+        LinearColor::slice_tonemap_to_encoded(&[sky.to_linear(); 2], &mut dst, 0.0, Tonemap::Clamp);
+        assert_eq!(dst, [sky; 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_tonemap_to_encoded_lengths() {
+        LinearColor::slice_tonemap_to_encoded(
+            &[LinearColor::default(); 2],
+            &mut [EncodedColor::CLEAR; 3],
+            0.0,
+            Tonemap::Clamp,
+        );
+    }
+}